@@ -0,0 +1,72 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use game_2048::bitboard::BitBoard;
+use game_2048::game::{BoardConfig, Game, Keypress, Tile};
+
+// square board sizes, from the classic game up to the largest boards worth playing
+const SIZES: [usize; 4] = [4, 16, 32, 64];
+
+// half full seeded board, most directions still move something
+fn half_full(size: usize) -> Game {
+    let config: BoardConfig = BoardConfig { width: size, height: size, count: size * size / 2, ..Default::default() };
+    return Game::new_game_seeded(Some(config), 2048).unwrap();
+}
+
+// full board of alternating 2s & 4s, nothing can move so every check has to scan the whole board
+fn stuck(size: usize) -> Game {
+    let config: BoardConfig = BoardConfig { width: size, height: size, count: 1, ..Default::default() };
+    let board: Vec<Vec<Tile>> = (0..size).map(|row| (0..size).map(|column| if (row + column) % 2 == 0 { 2 } else { 4 }).collect()).collect();
+    return Game::from_board(board, config, 0).unwrap();
+}
+
+fn apply_move(c: &mut Criterion) {
+    let mut group = c.benchmark_group("apply_move");
+    for size in SIZES {
+        let game: Game = half_full(size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &game, |b, game| {
+            b.iter_batched(|| game.clone(), |mut game| game.apply_move(Keypress::Left), BatchSize::SmallInput);
+        });
+    }
+    group.finish();
+}
+
+// packed 4x4 moves, against `apply_move/4`
+fn fast_apply(c: &mut Criterion) {
+    let game: Game = half_full(4);
+    c.bench_function("fast_apply/4", |b| {
+        b.iter_batched(|| game.clone(), |mut game| game.fast_apply(Keypress::Left), BatchSize::SmallInput);
+    });
+}
+
+// the packed move alone, what a search pays per simulated move
+fn bitboard_shift(c: &mut Criterion) {
+    let board: BitBoard = half_full(4).bitboard().unwrap();
+    c.bench_function("bitboard_shift/4", |b| b.iter(|| black_box(board).shift(Keypress::Left)));
+}
+
+// a move that changes nothing, so nothing is spawned or remembered
+fn apply_move_rejected(c: &mut Criterion) {
+    let mut group = c.benchmark_group("apply_move_rejected");
+    for size in SIZES {
+        let mut game: Game = half_full(size);
+        game.apply_move(Keypress::Left);
+        while game.can_move_left() == true {
+            game.apply_move(Keypress::Left);
+        }
+        group.bench_with_input(BenchmarkId::from_parameter(size), &game, |b, game| {
+            b.iter_batched(|| game.clone(), |mut game| game.apply_move(Keypress::Left), BatchSize::SmallInput);
+        });
+    }
+    group.finish();
+}
+
+fn game_over(c: &mut Criterion) {
+    let mut group = c.benchmark_group("game_over");
+    for size in SIZES {
+        let game: Game = stuck(size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &game, |b, game| b.iter(|| game.is_game_over()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, apply_move, fast_apply, bitboard_shift, apply_move_rejected, game_over);
+criterion_main!(benches);
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use crate::game::{Game, Keypress, Tile, WALL};
+
+// heuristic weights
+const EMPTY_WEIGHT: f64 = 2.7;
+const MONOTONICITY_WEIGHT: f64 = 1.0;
+const SMOOTHNESS_WEIGHT: f64 = 0.1;
+const MAX_TILE_WEIGHT: f64 = 1.0;
+const CORNER_WEIGHT: f64 = 2.0;
+
+// chance branches less likely than this are evaluated directly instead of expanded
+const PROBABILITY_CUTOFF: f64 = 0.0001;
+
+type Cache = HashMap<(u64, usize), f64>;
+
+// walls count as 0, they don't add to the tile values nor to the free space
+#[inline]
+fn log2(value: Tile) -> f64 {
+    if value == 0 || value == WALL { return 0.0; }
+    return (value as f64).log2();
+}
+
+// penalizes lines that go up and down instead of only one way
+fn line_monotonicity(line: &[f64]) -> f64 {
+    let mut increasing: f64 = 0.0;
+    let mut decreasing: f64 = 0.0;
+
+    for i in 1..line.len() {
+        if line[i - 1] > line[i] {
+            decreasing += line[i - 1] - line[i];
+        } else {
+            increasing += line[i] - line[i - 1];
+        }
+    }
+
+    return -increasing.min(decreasing);
+}
+
+// penalizes neighbouring tiles with very different values
+fn line_smoothness(line: &[f64]) -> f64 {
+    let tiles: Vec<f64> = line.iter().copied().filter(|&value| value != 0.0).collect();
+    let mut smoothness: f64 = 0.0;
+
+    for i in 1..tiles.len() {
+        smoothness -= (tiles[i] - tiles[i - 1]).abs();
+    }
+
+    return smoothness;
+}
+
+// board in log2 space, as rows and as columns
+fn log2_lines(board: &[Vec<Tile>]) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+    let height: usize = board.len();
+    let width: usize = board.first().map_or(0, |row| row.len());
+
+    let rows: Vec<Vec<f64>> = board.iter().map(|row| row.iter().map(|&value| log2(value)).collect()).collect();
+    let columns: Vec<Vec<f64>> = (0..width).map(|column| (0..height).map(|row| rows[row][column]).collect()).collect();
+    return (rows, columns);
+}
+
+// (monotonicity, smoothness) summed over every row & column
+fn shape(rows: &[Vec<f64>], columns: &[Vec<f64>]) -> (f64, f64) {
+    let mut monotonicity: f64 = 0.0;
+    let mut smoothness: f64 = 0.0;
+    for line in rows.iter().chain(columns.iter()) {
+        monotonicity += line_monotonicity(line);
+        smoothness += line_smoothness(line);
+    }
+
+    return (monotonicity, smoothness);
+}
+
+// static board evaluation, higher is better
+pub fn evaluate(board: &[Vec<Tile>]) -> f64 {
+    let (rows, columns) = log2_lines(board);
+    let (monotonicity, smoothness) = shape(&rows, &columns);
+
+    let empty: usize = board.iter().flatten().filter(|&&value| value == 0).count();
+    let max_tile: f64 = rows.iter().flatten().copied().fold(0.0, f64::max);
+
+    return EMPTY_WEIGHT * empty as f64
+        + MONOTONICITY_WEIGHT * monotonicity
+        + SMOOTHNESS_WEIGHT * smoothness
+        + MAX_TILE_WEIGHT * max_tile;
+}
+
+// how `healthy` a board looks to a player, monotonic lines and the largest tile in a corner
+// unlike `evaluate` it ignores the free space, it's only shown and never drives a move
+pub fn quality(board: &[Vec<Tile>]) -> f64 {
+    let (rows, columns) = log2_lines(board);
+    let (monotonicity, smoothness) = shape(&rows, &columns);
+
+    let max_tile: f64 = rows.iter().flatten().copied().fold(0.0, f64::max);
+    let last_row: usize = rows.len().saturating_sub(1);
+    let last_column: usize = columns.len().saturating_sub(1);
+    let in_corner: bool = [(0, 0), (0, last_column), (last_row, 0), (last_row, last_column)].iter()
+        .any(|&(row, column)| rows.get(row).and_then(|row| row.get(column)) == Some(&max_tile));
+
+    let corner: f64 = if in_corner { max_tile } else { 0.0 };
+    return MONOTONICITY_WEIGHT * monotonicity
+        + SMOOTHNESS_WEIGHT * smoothness
+        + CORNER_WEIGHT * corner;
+}
+
+// legal move whose resulting game is valued the highest
+fn best_move(game: &Game, mut value_of: impl FnMut(&Game) -> f64) -> Option<Keypress> {
+    let mut best: Option<(Keypress, f64)> = None;
+
+    for (direction, next) in game.successors() {
+        let value: f64 = value_of(&next);
+        if best.is_none_or(|(_, best_value)| value > best_value) {
+            best = Some((direction, value));
+        }
+    }
+
+    return best.map(|(direction, _)| direction);
+}
+
+// best move by the heuristic of the board right after the move, no lookahead
+pub fn greedy(game: &Game) -> Option<Keypress> {
+    return best_move(game, |next| evaluate(next.board()));
+}
+
+// player turn, picks the best move
+fn max_node(game: &Game, depth: usize, probability: f64, cache: &mut Cache) -> f64 {
+    let successors: Vec<(Keypress, Game)> = game.successors();
+    if successors.is_empty() { return evaluate(game.board()); }
+
+    return successors.iter()
+        .map(|(_, next)| chance_node(next, depth, probability, cache))
+        .fold(f64::NEG_INFINITY, f64::max);
+}
+
+// random turn, averages over every possible tile spawn
+fn chance_node(game: &Game, depth: usize, probability: f64, cache: &mut Cache) -> f64 {
+    if depth == 0 || probability < PROBABILITY_CUTOFF { return evaluate(game.board()); }
+
+    let key: (u64, usize) = (game.board_hash(), depth);
+    if let Some(&value) = cache.get(&key) { return value; }
+
+    let spawns: Vec<(f64, Game)> = game.spawns();
+    let value: f64 = if spawns.is_empty() {
+        evaluate(game.board())
+    } else {
+        spawns.iter()
+            .map(|(chance, next)| chance * max_node(next, depth - 1, probability * chance, cache))
+            .sum()
+    };
+
+    cache.insert(key, value);
+    return value;
+}
+
+// best move looking `depth` moves ahead, random spawns are modeled as chance nodes
+// depth 1 only looks at the board after the move, same as `greedy`
+pub fn expectimax(game: &Game, depth: usize) -> Option<Keypress> {
+    let mut cache: Cache = HashMap::new();
+    return best_move(game, |next| chance_node(next, depth.saturating_sub(1), 1.0, &mut cache));
+}
@@ -0,0 +1,189 @@
+use std::sync::OnceLock;
+
+use crate::game::{collapse_row, Keypress, Tile, DIRECTIONS};
+use crate::grid::Grid;
+
+// packed boards are always 4x4
+pub const SIZE: usize = 4;
+
+// biggest exponent a tile may start with, merging two of them still fits in 4 bits
+const MAX_EXPONENT: u32 = 14;
+
+// a classic 4x4 board packed into a `u64`, 4 bits per cell holding log2 of the tile, 0 for an empty cell
+// row `r` takes bits `16 * r .. 16 * r + 16`, column `c` of that row bits `4 * c .. 4 * c + 4`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitBoard(u64);
+
+// every possible packed row moved left & right, with the score & the largest merged exponent of the move
+struct RowTables {
+    left: Vec<u16>,
+    right: Vec<u16>,
+    left_score: Vec<u64>,
+    right_score: Vec<u64>,
+    left_merge: Vec<u8>,
+    right_merge: Vec<u8>
+}
+
+fn unpack_row(row: u16) -> [Tile; SIZE] {
+    let mut line: [Tile; SIZE] = [0; SIZE];
+    for i in 0..SIZE {
+        let exponent: u16 = (row >> (4 * i)) & 0xF;
+        line[i] = if exponent == 0 { 0 } else { 1 << exponent };
+    }
+    return line;
+}
+
+// exponents past 15 can't be packed, they only come from rows a packed board never starts with
+fn pack_row(line: &[Tile]) -> u16 {
+    let mut row: u16 = 0;
+    for i in 0..SIZE {
+        let exponent: u16 = if line[i] == 0 { 0 } else { line[i].ilog2() as u16 & 0xF };
+        row |= exponent << (4 * i);
+    }
+    return row;
+}
+
+// exponent of the largest tile the move made, a tile there's more of after the move than before
+fn merged_exponent(line: &[Tile], collapsed: &[Tile]) -> u8 {
+    let mut largest: Tile = 0;
+    for &tile in collapsed {
+        if tile <= largest { continue; }
+        let count = |line: &[Tile]| line.iter().filter(|&&other| other == tile).count();
+        if count(collapsed) > count(line) { largest = tile; }
+    }
+    return if largest == 0 { 0 } else { largest.ilog2() as u8 };
+}
+
+fn reverse_row(row: u16) -> u16 {
+    return (row >> 12) | ((row >> 4) & 0x00F0) | ((row << 4) & 0x0F00) | (row << 12);
+}
+
+// built once from `collapse_row`, so the packed moves follow the exact same rules as the regular ones
+fn row_tables() -> &'static RowTables {
+    static TABLES: OnceLock<RowTables> = OnceLock::new();
+    return TABLES.get_or_init(|| {
+        let mut left: Vec<u16> = vec![0; 1 << 16];
+        let mut right: Vec<u16> = vec![0; 1 << 16];
+        let mut left_score: Vec<u64> = vec![0; 1 << 16];
+        let mut right_score: Vec<u64> = vec![0; 1 << 16];
+        let mut left_merge: Vec<u8> = vec![0; 1 << 16];
+        let mut right_merge: Vec<u8> = vec![0; 1 << 16];
+
+        for row in 0..=u16::MAX {
+            let line: [Tile; SIZE] = unpack_row(row);
+            let (collapsed, gained) = collapse_row(&line, 2);
+            let moved: u16 = pack_row(&collapsed);
+            let merged: u8 = merged_exponent(&line, &collapsed);
+            left[row as usize] = moved;
+            left_score[row as usize] = gained;
+            left_merge[row as usize] = merged;
+
+            // moving right is moving the reversed row left
+            let reversed: usize = reverse_row(row) as usize;
+            right[reversed] = reverse_row(moved);
+            right_score[reversed] = gained;
+            right_merge[reversed] = merged;
+        }
+
+        return RowTables { left, right, left_score, right_score, left_merge, right_merge };
+    });
+}
+
+// swaps rows & columns, so columns can use the row tables
+fn transpose(board: u64) -> u64 {
+    let a1: u64 = board & 0xF0F0_0F0F_F0F0_0F0F;
+    let a2: u64 = board & 0x0000_F0F0_0000_F0F0;
+    let a3: u64 = board & 0x0F0F_0000_0F0F_0000;
+    let a: u64 = a1 | (a2 << 12) | (a3 >> 12);
+    let b1: u64 = a & 0xFF00_FF00_00FF_00FF;
+    let b2: u64 = a & 0x00FF_00FF_0000_0000;
+    let b3: u64 = a & 0x0000_0000_FF00_FF00;
+    return b1 | (b2 >> 24) | (b3 << 24);
+}
+
+impl BitBoard {
+    // `None` unless the grid is 4x4 with only empty cells & powers of two up to 2^14, walls can't be packed
+    pub fn from_grid(grid: &Grid) -> Option<Self> {
+        if grid.width() != SIZE || grid.height() != SIZE { return None; }
+
+        let mut board: u64 = 0;
+        for row in 0..SIZE {
+            for column in 0..SIZE {
+                let tile: Tile = grid[(row, column)];
+                if tile == 0 { continue; }
+                if tile.is_power_of_two() == false || tile == 1 || tile.ilog2() > MAX_EXPONENT { return None; }
+                board |= (tile.ilog2() as u64) << (16 * row + 4 * column);
+            }
+        }
+
+        return Some(BitBoard(board));
+    }
+
+    pub fn to_grid(self) -> Grid {
+        let mut grid: Grid = Grid::new(SIZE, SIZE);
+        self.write_to(&mut grid);
+        return grid;
+    }
+
+    // same as `to_grid` into a 4x4 grid that's already there, nothing is allocated
+    pub fn write_to(self, grid: &mut Grid) {
+        for row in 0..SIZE {
+            for (column, tile) in unpack_row(self.row(row)).into_iter().enumerate() {
+                grid[(row, column)] = tile;
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn row(self, row: usize) -> u16 {
+        return (self.0 >> (16 * row)) as u16;
+    }
+
+    pub fn empty_count(self) -> usize {
+        let mut empty: usize = 0;
+        for cell in 0..SIZE * SIZE {
+            if (self.0 >> (4 * cell)) & 0xF == 0 { empty += 1; }
+        }
+        return empty;
+    }
+
+    // the board after sliding & merging towards `direction` with the classic rules, and the score gained
+    // anything but a direction leaves the board as it is
+    pub fn shift(self, direction: Keypress) -> (BitBoard, u64) {
+        let (moved, score, _) = self.shift_merging(direction);
+        return (moved, score);
+    }
+
+    // `shift` with the largest tile the move merged, 0 if nothing merged
+    pub fn shift_merging(self, direction: Keypress) -> (BitBoard, u64, Tile) {
+        let tables: &RowTables = row_tables();
+        let (board, table, scores, merges): (u64, &[u16], &[u64], &[u8]) = match direction {
+            Keypress::Left => (self.0, &tables.left, &tables.left_score, &tables.left_merge),
+            Keypress::Right => (self.0, &tables.right, &tables.right_score, &tables.right_merge),
+            Keypress::Up => (transpose(self.0), &tables.left, &tables.left_score, &tables.left_merge),
+            Keypress::Down => (transpose(self.0), &tables.right, &tables.right_score, &tables.right_merge),
+            _ => return (self, 0, 0)
+        };
+
+        let mut moved: u64 = 0;
+        let mut score: u64 = 0;
+        let mut merged: u8 = 0;
+        for row in 0..SIZE {
+            let line: usize = ((board >> (16 * row)) & 0xFFFF) as usize;
+            moved |= (table[line] as u64) << (16 * row);
+            score += scores[line];
+            merged = merged.max(merges[line]);
+        }
+
+        if direction == Keypress::Up || direction == Keypress::Down {
+            moved = transpose(moved);
+        }
+        let largest_merge: Tile = if merged == 0 { 0 } else { 1 << merged };
+        return (BitBoard(moved), score, largest_merge);
+    }
+
+    // no direction changes the board
+    pub fn is_stuck(self) -> bool {
+        return DIRECTIONS.into_iter().all(|direction| self.shift(direction).0 == self);
+    }
+}
@@ -0,0 +1,38 @@
+use std::fmt;
+
+use crate::game::Tile;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameError {
+    EmptyBoard, // no tiles to start with
+    FullBoard, // every cell filled from the start
+    Overflow { count: usize, capacity: usize }, // more tiles than cells
+    InvalidDimensions { width: usize, height: usize },
+    InvalidBase(Tile), // tiles can't grow from a base below 2
+    InvalidProbability(f64), // spawn chance outside of 0.0..=1.0
+    BoardMismatch { width: usize, height: usize }, // given board doesn't have the configured size
+    InvalidTile(Tile), // given tile isn't a power of the base
+    InvalidTarget(Tile), // winning tile isn't a power of the base, it could never be made
+    InvalidSpawnWeight(f64), // negative or endless spawn weight, or weights that add up to nothing
+    InvalidCode // share code that `Game::export_code` couldn't have made
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::EmptyBoard => write!(f, "Empty board!"),
+            GameError::FullBoard => write!(f, "Full board!"),
+            GameError::Overflow { count, capacity } => write!(f, "Overflow! {} tiles don't fit in {} cells", count, capacity),
+            GameError::InvalidDimensions { width, height } => write!(f, "Invalid dimensions! {}x{} board", width, height),
+            GameError::InvalidBase(base) => write!(f, "Invalid base! {}", base),
+            GameError::InvalidProbability(chance) => write!(f, "Invalid probability! {} is outside of 0.0..=1.0", chance),
+            GameError::BoardMismatch { width, height } => write!(f, "Board mismatch! Board doesn't match the configured {}x{} size", width, height),
+            GameError::InvalidTile(tile) => write!(f, "Invalid tile! {}", tile),
+            GameError::InvalidTarget(target) => write!(f, "Invalid target! {} isn't a power of the base", target),
+            GameError::InvalidSpawnWeight(weight) => write!(f, "Invalid spawn weight! {}", weight),
+            GameError::InvalidCode => write!(f, "Invalid code! Not a shared board")
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
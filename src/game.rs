@@ -1,89 +1,386 @@
-use std::collections::{HashSet, HashMap};
-use owo_colors::OwoColorize;
+#[cfg(feature = "serde")]
+use std::{error::Error, fs, path::Path};
+use std::fmt;
+use std::io;
+use std::time::{Duration, Instant};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use owo_colors::Style;
 use rand::prelude::*;
-use comfy_table::{Color, Table, presets::UTF8_FULL, modifiers::UTF8_ROUND_CORNERS, Cell, CellAlignment};
+use rand::rngs::StdRng;
+use comfy_table::{Attribute, Color, Table, presets::{UTF8_FULL, ASCII_FULL}, modifiers::UTF8_ROUND_CORNERS, Cell, CellAlignment};
 use getch_rs::{Getch, Key};
+use unicode_width::UnicodeWidthStr;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+
+use crate::auto;
+use crate::bitboard::{self, BitBoard};
+use crate::error::GameError;
+use crate::grid::Grid;
+use crate::highscore;
+use crate::lang::Strings;
+use crate::keybindings::KeyBindings;
+use crate::render::{RenderOptions, Theme, TileGlyphs};
+use crate::replay::Recorder;
+
+pub type Position = (usize, usize); // (row, column)
+pub type Tile = u64; // tile value, wide enough for very deep games on big boards
+
+// immovable blocker tile, it never merges and splits its row & column into separate parts
+pub const WALL: Tile = Tile::MAX;
+
+// colors of one theme, (foreground, background) pairs
+// tiles repeat the foregrounds every `foregrounds.len()` doublings, each round on the next background
+struct Palette {
+    empty: (Color, Color),
+    wall: (Color, Color),
+    foregrounds: &'static [Color],
+    backgrounds: &'static [Color]
+}
+
+// 2..64 on black, 128..4096 on white, 8192..262144 on dark grey and so on
+const CLASSIC: Palette = Palette {
+    empty: (Color::White, Color::Black),
+    wall: (Color::White, Color::DarkRed),
+    foregrounds: &[Color::Grey, Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta],
+    backgrounds: &[Color::Black, Color::White, Color::DarkGrey, Color::DarkCyan, Color::DarkBlue]
+};
+
+// blues & greens, light text on dark backgrounds
+const OCEAN: Palette = Palette {
+    empty: (Color::White, Color::DarkBlue),
+    wall: (Color::White, Color::DarkGrey),
+    foregrounds: &[Color::White, Color::Cyan, Color::Green, Color::Yellow],
+    backgrounds: &[Color::DarkBlue, Color::Black, Color::DarkMagenta]
+};
+
+// black text on alternating light backgrounds, empty cells keep the terminal's own colors
+const MONO: Palette = Palette {
+    empty: (Color::Reset, Color::Reset),
+    wall: (Color::White, Color::DarkGrey),
+    foregrounds: &[Color::Black],
+    backgrounds: &[Color::White, Color::Grey]
+};
+
+fn theme_colors(theme: Theme) -> &'static Palette {
+    match theme {
+        Theme::Classic => return &CLASSIC,
+        Theme::Ocean => return &OCEAN,
+        Theme::Mono => return &MONO
+    }
+}
 
-use once_cell::sync::Lazy;
+// merged tiles light up in these for a moment with `RenderOptions::merge_flash`
+const MERGE_FLASH_COLORS: (Color, Color) = (Color::Black, Color::Yellow);
 
-type Position = (usize, usize);
-type Board = Vec<Vec<u32>>;
+// the tile spawned by the last move stands out with these until the next move
+const SPAWN_ATTRIBUTES: [Attribute; 2] = [Attribute::Bold, Attribute::Underlined];
 
-static TILE_COLORS: Lazy<HashMap<u32, (Color, Color)>> = Lazy::new(|| {
-    let mut colors: HashMap<u32, (Color, Color)> = HashMap::new();
+// (foreground, background) of a tile in `theme`, computed from log2(value) so every magnitude gets a color
+pub fn tile_style(value: Tile, theme: Theme) -> (Color, Color) {
+    let palette: &Palette = theme_colors(theme);
+    if value == 0 { return palette.empty; }
+    if value == WALL { return palette.wall; }
 
-    colors.insert(2, (Color::Grey, Color::Black));
-    colors.insert(4, (Color::Red, Color::Black));
-    colors.insert(8, (Color::Green, Color::Black));
-    colors.insert(16, (Color::Yellow, Color::Black));
-    colors.insert(32, (Color::Blue, Color::Black));
-    colors.insert(64, (Color::Magenta, Color::Black));
+    let index: usize = value.ilog2().saturating_sub(1) as usize;
+    let foreground: Color = palette.foregrounds[index % palette.foregrounds.len()];
+    let background: Color = palette.backgrounds[(index / palette.foregrounds.len()) % palette.backgrounds.len()];
+    return (foreground, background);
+}
 
-    colors.insert(128, (Color::Grey, Color::White));
-    colors.insert(256, (Color::Red, Color::White));
-    colors.insert(512, (Color::Green, Color::White));
-    colors.insert(1024, (Color::Yellow, Color::White));
-    colors.insert(2048, (Color::Blue, Color::White));
-    colors.insert(4096, (Color::Magenta, Color::White));
+// which pair merges when several pairs compete for the same tile, e.g. [2, 2, 2]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MergeResolution {
+    FirstWins, // pair closest to the edge the tiles move towards, [2, 2, 2] moved left is [4, 2, 0]
+    LastWins, // pair farthest from that edge, [2, 2, 2] moved left is [2, 4, 0]
+    HighestValue // highest valued pair first, ties fall back to `FirstWins`
+}
 
-    return colors;
-});
+// what happens once the merge budget runs out
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BudgetExhausted { Slide, GameOver }
+
+// frantic mode, every move has to come in time & the time shrinks as the game goes on, see `Game::move_time`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MoveTimer {
+    pub start: Duration, // time for the first move
+    pub decay: f64, // every successful move leaves the next one this fraction of the time, 0.0 < decay <= 1.0
+    pub floor: Duration, // the time never shrinks below this
+    pub penalty: TimeoutPenalty
+}
 
-#[derive(Debug)]
+// what a move that didn't come in time costs, see `Game::move_timeout`
+// `Spawn` - one tile spawns like after a move & the timer starts over, the game ends when there's no room for it
+// `GameOver` - the game ends right away
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TimeoutPenalty { Spawn, GameOver }
+
+// how the value of a spawned tile is picked
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SpawnMode {
+    Classic, // `base` or `base * base`, 2 or 4 by default
+    MatchMax, // same as the highest tile on the board, `base` on an empty board
+    Weighted // one of `BoardConfig::spawn_values`, picked by weight, the starting tiles too
+}
+
+// ruleset the other options are played under
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Mode {
+    #[default]
+    Normal,
+    Hardcore // no undo, the high score stays hidden & at least `HARDCORE_FOUR_CHANCE` of the spawns are 4s
+}
+
+// smallest `four_chance` a hardcore game spawns with
+const HARDCORE_FOUR_CHANCE: f64 = 0.2;
+
+impl Mode {
+    pub fn from_name(name: &str) -> Option<Mode> {
+        match name {
+            "normal" => Some(Mode::Normal),
+            "hardcore" => Some(Mode::Hardcore),
+            _ => None
+        }
+    }
+
+    // `false` - the undo key does nothing and no boards are kept for it
+    pub fn allows_undo(self) -> bool {
+        return self != Mode::Hardcore;
+    }
+
+    // `false` - the stored high score isn't shown beside the score, it's still saved
+    pub fn shows_best(self) -> bool {
+        return self != Mode::Hardcore;
+    }
+
+    // `four_chance` is raised to this when it's lower
+    pub fn min_four_chance(self) -> f64 {
+        match self {
+            Mode::Normal => 0.0,
+            Mode::Hardcore => HARDCORE_FOUR_CHANCE
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // missing fields keep their default, e.g. in the config file
 pub struct BoardConfig {
     pub width: usize,
     pub height: usize,
-    pub count: usize
+    pub count: usize,
+    pub merge_budget: Option<u32>, // total merges allowed for the whole game, `None` - unlimited
+    pub budget_exhausted: BudgetExhausted,
+    pub spawn_mode: SpawnMode,
+    pub spawn_values: Vec<(Tile, f64)>, // (value, weight) pairs for `SpawnMode::Weighted`, the weights don't have to add up to 1
+    pub merge_resolution: MergeResolution,
+    pub undo_depth: usize, // how many moves can be undone
+    pub target: Tile, // tile that wins the game
+    pub base: Tile, // smallest tile, merging multiplies by it - 2, 4, 8... for base 2, 3, 9, 27... for base 3
+    pub blockers: usize, // walls placed on the starting board
+    pub combo_scoring: bool, // merging moves in a row score more, see `Game::streak`
+    pub spawn_on_move: bool, // `false` - practice mode, moves only slide & merge the tiles already on the board
+    pub spawn_per_move: usize, // tiles spawned after every move, fewer when the board runs out of free cells
+    pub four_chance: f64, // chance of spawning a `base * base` tile (4) instead of a `base` tile (2)
+    pub time_limit: Option<Duration>, // timed game, it ends once the time runs out, `None` - no limit
+    pub move_timer: Option<MoveTimer>, // time limit per move, `None` - take as long as you like
+    pub gravity: bool, // puzzle mode, tiles always fall down, see `Game::rotate_columns`
+    pub adaptive_spawn: bool, // `four_chance` grows with the largest tile, see `Game::four_chance`
+    pub wrap: bool, // toroidal board, the two ends of every row & column are neighbours, see `wrap_around`
+    pub mode: Mode,
+    pub stop_at_target: bool // reaching `target` ends the game with `GameOver` instead of a `Won` & playing on
 }
 impl Default for BoardConfig {
     fn default() -> Self {
         BoardConfig {
             width: 4,
             height: 4,
-            count: 2
+            count: 2,
+            merge_budget: None,
+            budget_exhausted: BudgetExhausted::Slide,
+            spawn_mode: SpawnMode::Classic,
+            spawn_values: vec![],
+            merge_resolution: MergeResolution::FirstWins,
+            undo_depth: 8,
+            target: 2048,
+            base: 2,
+            blockers: 0,
+            combo_scoring: false,
+            spawn_on_move: true,
+            spawn_per_move: 1,
+            four_chance: 0.1,
+            time_limit: None,
+            move_timer: None,
+            gravity: false,
+            adaptive_spawn: false,
+            wrap: false,
+            mode: Mode::Normal,
+            stop_at_target: false
         }
     }
 }
 
 #[derive(Debug)]
-pub enum GameResult { GameOver, Exit, NoMove, NextMove, Reset, UnknownKeyPress }
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GameResult {
+    GameOver, TimeUp, Won, Exit, NoMove, Reset, ConfirmReset, Save, Undone, Resized, Hint(Keypress), UnknownKeyPress,
+    Paused, Resumed, // `Paused` - the game got paused or a key was ignored because it is
+    Transposed, // rows & columns swapped by the debug key
+    SandboxEntered, SandboxLeft, Edited, // `Edited` - the sandbox cursor moved or a tile was placed
+    TimedOut { spawned: (Position, Tile) }, // the move timer ran out & the penalty tile spawned, see `Game::move_timeout`
+    Solution { moves: Vec<Keypress>, score: u64 }, // what `Game::solve` played from here, the game itself didn't change
+    NextMove { summary: MoveSummary, spawned: Option<(Position, Tile)> } // merges of the move & the first new tile, `None` when nothing spawned
+}
+
+// what the merges of a single move added up to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MoveSummary {
+    pub merges: u32,
+    pub points: u64, // score gained by the move, combo bonus included
+    pub largest_merge: Tile // largest tile a merge made, 0 - nothing merged
+}
+
+// adaptive spawns add this much to `four_chance` per doubling of the largest tile past `base * base`
+const ADAPTIVE_CHANCE_STEP: f64 = 0.01;
+// adaptive spawns never go past this, unless `four_chance` already starts higher
+const ADAPTIVE_CHANCE_CAP: f64 = 0.3;
+
+// chance of spawning a `base * base` tile with the current largest tile
+fn four_chance(config: &BoardConfig, max_tile: Tile) -> f64 {
+    let chance: f64 = config.four_chance.max(config.mode.min_four_chance());
+    if config.adaptive_spawn == false || max_tile <= config.base * config.base { return chance; }
+
+    let doublings: u32 = max_tile.ilog(config.base) - 2;
+    return (chance + ADAPTIVE_CHANCE_STEP * doublings as f64).min(ADAPTIVE_CHANCE_CAP.max(chance));
+}
 
 #[inline]
-fn random_tile() -> u32 {
-    // 4 Tile (10%), 2 Tile (90%)
-    if thread_rng().gen_bool(1.0 / 10.0) == true {
-        return 4;
+fn random_tile(config: &BoardConfig, max_tile: Tile, rng: &mut StdRng) -> Tile {
+    // 4 Tile (10% by default), 2 Tile (90% by default)
+    if rng.gen_bool(four_chance(config, max_tile)) == true {
+        return config.base * config.base;
     } else {
-        return 2;
+        return config.base;
     };
 }
 
-fn random_board(config: &BoardConfig) -> Result<Board, &'static str> {
-    if config.count == 0 { return Err("Empty board!"); }
-    if config.count == config.width * config.height { return Err("Full board!"); }
-    if config.count > config.width * config.height { return Err("Overflow!"); }
+// one of `values`, each as likely as its share of the total weight
+fn weighted_tile(values: &[(Tile, f64)], rng: &mut StdRng) -> Tile {
+    let total: f64 = values.iter().map(|&(_, weight)| weight).sum();
+    let mut pick: f64 = rng.gen_range(0.0..total);
+    for &(value, weight) in values {
+        if pick < weight { return value; }
+        pick -= weight;
+    }
+
+    // rounding can leave a sliver past the last weight
+    return values.last().map_or(0, |&(value, _)| value);
+}
+
+// spawned tile values paired with their probability
+fn spawn_distribution(config: &BoardConfig, max_tile: Tile) -> Vec<(Tile, f64)> {
+    match config.spawn_mode {
+        SpawnMode::Classic => {
+            let chance: f64 = four_chance(config, max_tile);
+            vec![(config.base, 1.0 - chance), (config.base * config.base, chance)]
+        },
+        SpawnMode::MatchMax => vec![(max_tile.max(config.base), 1.0)],
+        SpawnMode::Weighted => {
+            let total: f64 = config.spawn_values.iter().map(|&(_, weight)| weight).sum();
+            config.spawn_values.iter().map(|&(value, weight)| (value, weight / total)).collect()
+        }
+    }
+}
+
+fn spawn_tile(config: &BoardConfig, max_tile: Tile, rng: &mut StdRng) -> Tile {
+    match config.spawn_mode {
+        SpawnMode::Classic => random_tile(config, max_tile, rng),
+        SpawnMode::MatchMax => max_tile.max(config.base),
+        SpawnMode::Weighted => weighted_tile(&config.spawn_values, rng)
+    }
+}
+
+// weighted spawns need at least one value, every value a power of the base & weights that add up to something
+fn validate_spawn_values(config: &BoardConfig) -> Result<(), GameError> {
+    if config.spawn_mode != SpawnMode::Weighted { return Ok(()); }
+
+    for &(value, weight) in &config.spawn_values {
+        if is_power_of(value, config.base) == false { return Err(GameError::InvalidTile(value)); }
+        if weight.is_finite() == false || weight < 0.0 { return Err(GameError::InvalidSpawnWeight(weight)); }
+    }
+    let total: f64 = config.spawn_values.iter().map(|&(_, weight)| weight).sum();
+    if total.is_finite() == false || total <= 0.0 { return Err(GameError::InvalidSpawnWeight(total)); }
+    return Ok(());
+}
+
+// tile rules every board has to follow, generated or not
+fn validate_rules(config: &BoardConfig) -> Result<(), GameError> {
+    if config.base < 2 { return Err(GameError::InvalidBase(config.base)); }
+    if (0.0..=1.0).contains(&config.four_chance) == false { return Err(GameError::InvalidProbability(config.four_chance)); }
+    if is_power_of(config.target, config.base) == false { return Err(GameError::InvalidTarget(config.target)); }
+    validate_spawn_values(config)?;
+    if let Some(timer) = config.move_timer {
+        if timer.decay <= 0.0 || timer.decay > 1.0 || timer.decay.is_nan() { return Err(GameError::InvalidProbability(timer.decay)); }
+    }
+    return Ok(());
+}
+
+// `base`, `base * base`, ... are the only tiles a game can produce
+fn is_power_of(mut tile: Tile, base: Tile) -> bool {
+    if tile < base { return false; }
+    while tile.is_multiple_of(base) {
+        tile /= base;
+    }
+
+    return tile == 1;
+}
 
-    // get `count` unique positions on the board
-    let mut unique_positions: HashSet<Position> = HashSet::<Position>::with_capacity(config.count);
-    while unique_positions.len() != config.count {
-        let position: Position = (thread_rng().gen_range(0..config.height), thread_rng().gen_range(0..config.width));
+fn random_board(config: &BoardConfig, rng: &mut StdRng) -> Result<Grid, GameError> {
+    if config.width == 0 || config.height == 0 { return Err(GameError::InvalidDimensions { width: config.width, height: config.height }); }
+    let capacity: usize = config.width.checked_mul(config.height).ok_or(GameError::InvalidDimensions { width: config.width, height: config.height })?;
+    let occupied: usize = config.count.checked_add(config.blockers).ok_or(GameError::Overflow { count: usize::MAX, capacity })?;
+    if config.count == 0 { return Err(GameError::EmptyBoard); }
+    if occupied == capacity { return Err(GameError::FullBoard); }
+    if occupied > capacity { return Err(GameError::Overflow { count: occupied, capacity }); }
+    validate_rules(config)?;
+
+    // get `count` unique positions on the board, followed by the wall positions
+    // kept in insertion order, so the same seed always fills the same tiles
+    let mut unique_positions: Vec<Position> = Vec::<Position>::with_capacity(occupied);
+    while unique_positions.len() != occupied {
+        let position: Position = (rng.gen_range(0..config.height), rng.gen_range(0..config.width));
         if unique_positions.contains(&position) == false {
-            unique_positions.insert(position);
+            unique_positions.push(position);
         }
     }
 
     // allocate board
-    let mut board: Board = vec![vec![0; config.width]; config.height];
+    let mut board: Grid = Grid::new(config.width, config.height);
+
+    // walls go in before the tiles, spawning skips them when looking for the highest tile
+    let walls: Vec<Position> = unique_positions.split_off(config.count);
+    for (row, column) in walls {
+        board[(row, column)] = WALL;
+    }
 
     // generate board values from positions
     for position in unique_positions {
-        board[position.0][position.1] = random_tile();
+        let max_tile: Tile = board.tiles().filter(|&tile| tile != WALL).max().unwrap_or(0);
+        board[position] = spawn_tile(config, max_tile, rng);
     }
 
     return Ok(board);
 }
 
-fn move_zeroes_end(array: &mut Vec<u32>) {
+fn move_zeroes_end(array: &mut [Tile]) {
     if array.is_empty() { return; }
 
     let mut j: usize = 0;
@@ -95,344 +392,1748 @@ fn move_zeroes_end(array: &mut Vec<u32>) {
     }
 }
 
-fn move_zeroes_start(array: &mut Vec<u32>) {
-    if array.is_empty() { return; }
+// a tile only merges while the merged value still fits, the largest tiles stay as they are instead of wrapping around
+fn can_grow(tile: Tile, base: Tile) -> bool {
+    return tile.checked_mul(base).is_some_and(|merged| merged != WALL);
+}
 
-    let mut j: usize = array.len() - 1;
-    for i in (0..array.len()).rev() {
-        if array[i] != 0 {
-            (array[i], array[j]) = (array[j], array[i]);
-            j -= 1;
-        }
+// pairs of (destination, source) indices that merge in `line`, in the order they should be applied
+// index 0 is the edge the tiles move towards, zeroes between tiles are skipped
+fn merge_pairs(line: &[Tile], base: Tile, resolution: MergeResolution) -> Vec<(usize, usize)> {
+    let tiles: Vec<usize> = (0..line.len()).filter(|&i| line[i] != 0).collect();
+
+    // every neighbouring pair of equal tiles, some of them share a tile
+    let mut candidates: Vec<(usize, usize)> = tiles.windows(2)
+        .filter(|pair| line[pair[0]] == line[pair[1]] && can_grow(line[pair[0]], base))
+        .map(|pair| (pair[0], pair[1]))
+        .collect();
+
+    match resolution {
+        MergeResolution::FirstWins => {},
+        MergeResolution::LastWins => candidates.reverse(),
+        MergeResolution::HighestValue => candidates.sort_by(|a, b| line[b.0].cmp(&line[a.0]))
+    }
+
+    // a tile can only take part in one merge
+    let mut merged: Vec<bool> = vec![false; line.len()];
+    let mut pairs: Vec<(usize, usize)> = vec![];
+    for (destination, source) in candidates {
+        if merged[destination] || merged[source] { continue; }
+
+        merged[destination] = true;
+        merged[source] = true;
+        pairs.push((destination, source));
     }
+
+    return pairs;
 }
 
-fn equal_boards(a: &Board, b: &Board) -> bool {
-    if a.len() != b.len() { return false; }
+// outcome of collapsing a single line
+struct Collapse {
+    line: Vec<Tile>,
+    score: u64,
+    merges: Vec<(usize, Tile)>, // index in the collapsed line & new value of every merged tile
+    slides: Vec<(usize, usize)> // index before & after the collapse of every tile, merged ones included
+}
 
-    for row in 0..a.len() {
-        if a[row].len() != b[row].len() { return false; }
+// merges & compacts a line towards index 0, at most `merge_limit` merges happen (`None` - unlimited)
+fn collapse_segment(line: &[Tile], base: Tile, resolution: MergeResolution, merge_limit: Option<u32>) -> Collapse {
+    let mut collapsed: Vec<Tile> = line.to_vec();
+    let mut score: u64 = 0;
+    let mut pairs: Vec<(usize, usize)> = vec![];
 
-        for i in 0..a[row].len() {
-            if a[row][i] != b[row][i] { return false; }
-        }
+    for (destination, source) in merge_pairs(line, base, resolution) {
+        if merge_limit.is_some_and(|limit| pairs.len() as u32 >= limit) { break; }
+
+        collapsed[destination] *= base;
+        score = score.saturating_add(collapsed[destination]);
+        collapsed[source] = 0;
+        pairs.push((destination, source));
     }
 
-    return true;
+    // a tile ends up after every tile in front of it
+    let final_index = |index: usize| collapsed[..index].iter().filter(|&&tile| tile != 0).count();
+    let merges: Vec<(usize, Tile)> = pairs.iter()
+        .map(|&(destination, _)| (final_index(destination), collapsed[destination]))
+        .collect();
+
+    // a merged away tile slides to where its partner ends up
+    let slides: Vec<(usize, usize)> = (0..line.len())
+        .filter(|&index| line[index] != 0)
+        .map(|index| {
+            let target: usize = pairs.iter().find(|&&(_, source)| source == index).map_or(index, |&(destination, _)| destination);
+            (index, final_index(target))
+        })
+        .collect();
+
+    move_zeroes_end(&mut collapsed);
+    return Collapse { line: collapsed, score, merges, slides };
 }
 
-enum Keypress { Up, Down, Left, Right, Reset, Quit }
-impl TryFrom<Key> for Keypress {
-    type Error = &'static str;
-    fn try_from(value: Key) -> Result<Self, Self::Error> {
+// wrap mode, after the regular collapse the last tile meets the first one across the edge
+// both have to be untouched by this move's merges, the leading edge's own pairs always go first
+// `[2, 4, 8, 2]` moved left is `[4, 4, 8, 0]`, `[2, 2, 4, 2]` is `[4, 4, 2, 0]`
+fn wrap_around(collapse: &mut Collapse, base: Tile, merge_limit: Option<u32>) {
+    if merge_limit.is_some_and(|limit| collapse.merges.len() as u32 >= limit) { return; }
+
+    let tiles: usize = collapse.line.iter().filter(|&&tile| tile != 0).count();
+    if tiles < 2 { return; }
+    let last: usize = tiles - 1;
+    if collapse.line[0] != collapse.line[last] || can_grow(collapse.line[0], base) == false { return; }
+    if collapse.merges.iter().any(|&(index, _)| index == 0 || index == last) { return; }
+
+    collapse.line[0] *= base;
+    collapse.line[last] = 0;
+    collapse.score = collapse.score.saturating_add(collapse.line[0]);
+    collapse.merges.push((0, collapse.line[0]));
+    for slide in collapse.slides.iter_mut() {
+        if slide.1 == last { slide.1 = 0; }
+    }
+}
 
-        match value {
-            Key::Char('w') | Key::Char('W') | Key::Up => Ok(Keypress::Up),
-            Key::Char('s') | Key::Char('S') | Key::Down => Ok(Keypress::Down),
-            Key::Char('a') | Key::Char('A') | Key::Left => Ok(Keypress::Left),
-            Key::Char('d') | Key::Char('D') | Key::Right => Ok(Keypress::Right),
-            Key::Char('r') | Key::Char('R') => Ok(Keypress::Reset),
-            Key::Char('q') | Key::Char('Q') | Key::Esc => Ok(Keypress::Quit),
-            _ => Err("Invalid Key")
+// walls split the line into segments that collapse on their own, the walls stay where they are
+// walls are part of the slides too, so the slides alone describe the whole line before the move
+// `wrap` joins both ends of a line without walls, see `wrap_around`
+fn collapse_line(line: &[Tile], base: Tile, resolution: MergeResolution, merge_limit: Option<u32>, wrap: bool) -> Collapse {
+    let mut collapsed: Vec<Tile> = Vec::with_capacity(line.len());
+    let mut score: u64 = 0;
+    let mut merges: Vec<(usize, Tile)> = vec![];
+    let mut slides: Vec<(usize, usize)> = vec![];
+
+    for segment in line.split(|&tile| tile == WALL) {
+        let start: usize = collapsed.len();
+        let remaining: Option<u32> = merge_limit.map(|limit| limit - merges.len() as u32);
+        let part: Collapse = collapse_segment(segment, base, resolution, remaining);
+
+        collapsed.extend(part.line);
+        score = score.saturating_add(part.score);
+        merges.extend(part.merges.into_iter().map(|(index, value)| (start + index, value)));
+        slides.extend(part.slides.into_iter().map(|(from, to)| (start + from, start + to)));
+        if collapsed.len() < line.len() {
+            slides.push((collapsed.len(), collapsed.len()));
+            collapsed.push(WALL);
         }
+    }
 
+    let mut collapse: Collapse = Collapse { line: collapsed, score, merges, slides };
+    if wrap == true && line.contains(&WALL) == false {
+        wrap_around(&mut collapse, base, merge_limit);
     }
+    return collapse;
 }
 
-#[derive(Debug)]
+// collapses a row towards its start with the classic rules
+// returns the new row and the score gained, `collapse_row(&[2, 2, 2, 2], 2) == (vec![4, 4, 0, 0], 8)`
+pub fn collapse_row(row: &[Tile], base: Tile) -> (Vec<Tile>, u64) {
+    return collapse_row_with(row, base, MergeResolution::FirstWins, false);
+}
+
+// collapses a row towards its end when `reverse`, like moving right or down, towards its start otherwise
+// `resolution` picks the pair that merges when pairs compete, counted from the edge the tiles move towards
+// `[2, 2, 2]` is `[4, 2, 0]` with `FirstWins` and `[2, 4, 0]` with `LastWins`, reversed `[0, 2, 4]` and `[0, 4, 2]`
+pub fn collapse_row_with(row: &[Tile], base: Tile, resolution: MergeResolution, reverse: bool) -> (Vec<Tile>, u64) {
+    if reverse == false {
+        let collapse: Collapse = collapse_line(row, base, resolution, None, false);
+        return (collapse.line, collapse.score);
+    }
+
+    let reversed: Vec<Tile> = row.iter().rev().copied().collect();
+    let collapse: Collapse = collapse_line(&reversed, base, resolution, None, false);
+    return (collapse.line.into_iter().rev().collect(), collapse.score);
+}
+
+// centers `value` in `width` terminal columns, counting wide glyphs by their display width
+fn pad_to_width(value: &str, width: usize) -> String {
+    let padding: usize = width.saturating_sub(value.width());
+    let left: usize = padding / 2;
+    let right: usize = padding - left;
+    return format!("{}{}{}", " ".repeat(left), value, " ".repeat(right));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Keypress { Up, Down, Left, Right, Reset, Save, Undo, Hint, Solve, Quit, Wider, Narrower, Taller, Shorter, Pause, Transpose, Sandbox, Place(u8), Skip } // `Place` - exponent of the tile the sandbox puts under the cursor, 0 clears it, `Skip` - only the tutorial uses it
+
+pub const DIRECTIONS: [Keypress; 4] = [Keypress::Left, Keypress::Right, Keypress::Up, Keypress::Down];
+
+// `Game::solve` gives up after this many moves, without spawns the tiles could slide back & forth forever
+const SOLVE_MOVE_LIMIT: usize = 100_000;
+
+// first byte of a share code, a new layout gets a new version
+const CODE_VERSION: u8 = 1;
+// exponent byte of a wall in a share code, real exponents stay below 64
+const CODE_WALL: u8 = u8::MAX;
+
+// one byte per cell, 0 - empty, the exponent of a tile, `CODE_WALL` - wall
+fn exponent_byte(tile: Tile, base: Tile) -> u8 {
+    match tile {
+        0 => return 0,
+        WALL => return CODE_WALL,
+        tile => return tile.ilog(base) as u8
+    }
+}
+
+// 64 bit FNV-1a, spelled out so the hashes stay the same across rust versions & platforms
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+// little endian, 7 bits per byte, the high bit marks that another byte follows
+fn push_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+// `None` when the bytes run out in the middle of a number
+fn read_varint(bytes: &mut impl Iterator<Item = u8>) -> Option<u64> {
+    let mut value: u64 = 0;
+    for shift in (0..64).step_by(7) {
+        let byte: u8 = bytes.next()?;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 { return Some(value); }
+    }
+    return None;
+}
+
+// everything needed to restore a game, written as json
+// also what `Game` itself serializes to, the rng, history & hooks aren't part of it
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct SaveFile {
+    config: BoardConfig,
+    board: Grid,
+    score: u64,
+    moves: u32,
+    has_won: bool,
+    merges_remaining: Option<u32>
+}
+
+// the state printed by `display_game` in json mode
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct StateDump<'a> {
+    board: &'a Grid,
+    score: u64,
+    moves: u32,
+    over: bool,
+    last_spawn: Option<SpawnDump>, // tile spawned by the last move, `null` when nothing did
+    four_chance: f64 // chance that the next spawned tile is `base * base`
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct SpawnDump {
+    row: usize,
+    col: usize,
+    value: Tile
+}
+
+// called with the destination & new value of every merged tile
+// clones start without it, so simulated moves (hints, AI) don't trigger it
+#[derive(Default)]
+struct MergeHook(Option<Box<dyn FnMut(Position, Tile)>>);
+
+impl Clone for MergeHook {
+    fn clone(&self) -> Self {
+        return MergeHook(None);
+    }
+}
+
+impl fmt::Debug for MergeHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "MergeHook({})", if self.0.is_some() { "set" } else { "none" });
+    }
+}
+
+// builds a game from the default config, only what's set changes, a random seed unless `seed` is set
+#[derive(Debug, Clone, Default)]
+pub struct GameBuilder {
+    config: BoardConfig,
+    seed: Option<u64>,
+    render: RenderOptions
+}
+
+impl GameBuilder {
+    // starts over from a whole config, the setters after it still apply
+    pub fn config(mut self, config: BoardConfig) -> Self {
+        self.config = config;
+        return self;
+    }
+
+    pub fn width(mut self, width: usize) -> Self {
+        self.config.width = width;
+        return self;
+    }
+
+    pub fn height(mut self, height: usize) -> Self {
+        self.config.height = height;
+        return self;
+    }
+
+    pub fn count(mut self, count: usize) -> Self {
+        self.config.count = count;
+        return self;
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        return self;
+    }
+
+    pub fn target(mut self, target: Tile) -> Self {
+        self.config.target = target;
+        return self;
+    }
+
+    pub fn four_chance(mut self, four_chance: f64) -> Self {
+        self.config.four_chance = four_chance;
+        return self;
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.render.theme = theme;
+        return self;
+    }
+
+    // checked the same way as `new_game_seeded`
+    pub fn build(self) -> Result<Game, GameError> {
+        let seed: u64 = self.seed.unwrap_or_else(|| thread_rng().gen());
+        let mut game: Game = Game::new_game_seeded(Some(self.config), seed)?;
+        game.render = self.render;
+        return Ok(game);
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Game {
     pub config: BoardConfig,
-    board: Vec<Vec<u32>>,
-    score: u32
+    pub render: RenderOptions,
+    pub key_bindings: KeyBindings,
+    pub glyphs: TileGlyphs,
+    pub confirm_reset: bool, // reset asks first, only a following `y` key goes through with it
+    reset_pending: bool, // reset asked, waiting for the answer
+    paused: bool, // only the pause, reset & quit keys do anything, e.g. so stray keys don't count while streaming
+    sandbox: bool, // the keys edit the board instead of playing it, see `edit`
+    cursor: Position, // cell the sandbox places tiles in
+    board: Grid,
+    score: u64,
+    moves: u32, // successful moves only
+    has_won: bool, // target reached, the game continues but `Won` isn't reported again
+    merges_remaining: Option<u32>,
+    streak: u32, // merging moves in a row, only tracked with `combo_scoring`
+    slides: Vec<(Position, Position, Tile)>, // every tile & wall of the last move, where it started, where it ended & its value before merging
+    merged: Vec<(Position, Tile)>, // merge destinations & new values of the last move
+    last_spawn: Option<Position>, // tile spawned by the last move, highlighted until the next one
+    history: Vec<Snapshot>, // state before each of the last moves, newest last
+    score_history: Vec<u64>, // total score after every successful move, undone moves stay in it
+    tile_history: Vec<Tile>, // largest tile after every successful move, next to `score_history`
+    best: Option<u64>, // stored high score, shown once loaded
+    recorder: Option<Recorder>,
+    on_merge: MergeHook,
+    started_at: Instant,
+    move_started_at: Instant, // when the move timer started for the current move
+    timed_out: bool, // a move didn't come in time & `TimeoutPenalty::GameOver` ended the game
+    seed: u64,
+    rng: StdRng
+}
+
+// everything a move changes, so `undo` puts it all back
+#[derive(Debug, Clone)]
+struct Snapshot {
+    board: Grid,
+    score: u64,
+    moves: u32,
+    has_won: bool,
+    merges_remaining: Option<u32>
+}
+
+// same board, score & rules, how the game got there (history, timers, rng) & how it's drawn don't count
+impl PartialEq for Game {
+    fn eq(&self, other: &Self) -> bool {
+        return self.board == other.board && self.score == other.score && self.config == other.config;
+    }
 }
 
 impl Game {
 
+    // the score stops at `u64::MAX` instead of wrapping around, see `score_capped`
+    #[inline(always)]
+    fn apply_score(&mut self, value: u64) {
+        self.score = self.score.saturating_add(value);
+    }
+
+    // with combo scoring, the points of a merging move are multiplied by `1 + streak / 10`
+    // a move that merges always scores, so no points means the combo is over
+    fn score_streak(&mut self, gained: u64) {
+        if self.config.combo_scoring == false { return; }
+        if gained == 0 {
+            self.streak = 0;
+            return;
+        }
+
+        let boosted: u64 = (gained as f64 * (1.0 + self.streak as f64 / 10.0)) as u64;
+        self.apply_score(boosted - gained);
+        self.streak += 1;
+    }
+
+    #[inline(always)]
+    fn merges_allowed(&self) -> bool {
+        return self.merges_remaining != Some(0);
+    }
+
     #[inline(always)]
-    fn apply_score(&mut self, value: u32) {
-        self.score += value;
+    fn spend_merges(&mut self, merges: u32) {
+        if let Some(remaining) = self.merges_remaining.as_mut() {
+            *remaining -= merges;
+        }
+    }
+
+    pub fn new_game(board_config: Option<BoardConfig>) -> Result<Self, GameError> {
+        return Game::builder().config(board_config.unwrap_or_default()).build();
+    }
+
+    // chainable setup, e.g. `Game::builder().width(5).seed(42).target(512).build()`
+    pub fn builder() -> GameBuilder {
+        return GameBuilder::default();
     }
 
-    pub fn new_game(board_config: Option<BoardConfig>) -> Result<Self, &'static str> {
+    // same seed and same moves always produce the same boards
+    pub fn new_game_seeded(board_config: Option<BoardConfig>, seed: u64) -> Result<Self, GameError> {
         let config: BoardConfig = board_config.unwrap_or_default();
-        let board: Board = random_board(&config)?;
-        return Ok(Self { config, board, score: 0 });
+        let mut rng: StdRng = StdRng::seed_from_u64(seed);
+        let board: Grid = random_board(&config, &mut rng)?;
+        let merges_remaining: Option<u32> = config.merge_budget;
+        return Ok(Self {
+            config,
+            render: RenderOptions::default(),
+            key_bindings: KeyBindings::default(),
+            glyphs: TileGlyphs::default(),
+            confirm_reset: false,
+            reset_pending: false,
+            paused: false,
+            sandbox: false,
+            cursor: (0, 0),
+            board,
+            score: 0,
+            moves: 0,
+            has_won: false,
+            merges_remaining,
+            streak: 0,
+            slides: vec![],
+            merged: vec![],
+            last_spawn: None,
+            history: vec![],
+            score_history: vec![],
+            tile_history: vec![],
+            best: None,
+            recorder: None,
+            on_merge: MergeHook::default(),
+            started_at: Instant::now(),
+            move_started_at: Instant::now(),
+            timed_out: false,
+            seed,
+            rng
+        });
     }
 
-    fn game_over(&self) -> bool {
+    // starts from an exact board instead of a random one, e.g. puzzles or reproducing a bug
+    pub fn from_board(board: impl Into<Grid>, config: BoardConfig, score: u64) -> Result<Self, GameError> {
+        let board: Grid = board.into();
+        validate_rules(&config)?;
+        if config.width == 0 || config.height == 0 {
+            return Err(GameError::InvalidDimensions { width: config.width, height: config.height });
+        }
+        if board.height() != config.height || board.rows().iter().any(|row| row.len() != config.width) {
+            return Err(GameError::BoardMismatch { width: config.width, height: config.height });
+        }
+        if let Some(tile) = board.tiles().find(|&tile| tile != 0 && tile != WALL && is_power_of(tile, config.base) == false) {
+            return Err(GameError::InvalidTile(tile));
+        }
 
-        // if there are zeroes on the board, its not a game over
-        for i in 0..self.config.height {
-            for j in 0..self.config.width {
-                if self.board[i][j] == 0 { return false; }
-            }
+        let seed: u64 = thread_rng().gen();
+        let has_won: bool = board.tiles().any(|tile| tile != WALL && tile >= config.target); // `Won` is only for reaching the target during play
+        let merges_remaining: Option<u32> = config.merge_budget;
+        return Ok(Self {
+            config,
+            render: RenderOptions::default(),
+            key_bindings: KeyBindings::default(),
+            glyphs: TileGlyphs::default(),
+            confirm_reset: false,
+            reset_pending: false,
+            paused: false,
+            sandbox: false,
+            cursor: (0, 0),
+            board,
+            score,
+            moves: 0,
+            has_won,
+            merges_remaining,
+            streak: 0,
+            slides: vec![],
+            merged: vec![],
+            last_spawn: None,
+            history: vec![],
+            score_history: vec![],
+            tile_history: vec![],
+            best: None,
+            recorder: None,
+            on_merge: MergeHook::default(),
+            started_at: Instant::now(),
+            move_started_at: Instant::now(),
+            timed_out: false,
+            seed,
+            rng: StdRng::seed_from_u64(seed)
+        });
+    }
+
+    #[inline(always)]
+    pub fn seed(&self) -> u64 {
+        return self.seed;
+    }
+
+    // starts over with the same config, settings & hooks carry over
+    // without `reseed` the original seed is reused, so the exact same starting board comes back
+    pub fn reset(&mut self, reseed: bool) -> Result<(), GameError> {
+        let seed: u64 = if reseed { thread_rng().gen() } else { self.seed };
+        let mut fresh: Game = Game::new_game_seeded(Some(self.config.clone()), seed)?;
+
+        fresh.render = self.render;
+        fresh.key_bindings = self.key_bindings.clone();
+        fresh.glyphs = self.glyphs.clone();
+        fresh.confirm_reset = self.confirm_reset;
+        fresh.best = self.best;
+        fresh.on_merge = std::mem::take(&mut self.on_merge);
+        if self.recorder.is_some() {
+            fresh.start_recording();
         }
 
-        // check if theres a possible move, if there is not then its game over
+        *self = fresh;
+        return Ok(());
+    }
 
-        // left move
-        for row in 0..self.config.height {
-            for i in 0..self.config.width {
-                if self.board[row][i] == 0 { continue; }
+    // records every keypress from now on, meant to be called right after the game is created
+    pub fn start_recording(&mut self) {
+        self.recorder = Some(Recorder::new(self.seed, self.config.clone()));
+    }
+
+    pub fn recorder(&self) -> Option<&Recorder> {
+        return self.recorder.as_ref();
+    }
+
+    // `f` gets the destination & new value of every merge, e.g. for animations or sounds
+    pub fn set_on_merge(&mut self, f: impl FnMut(Position, Tile) + 'static) {
+        self.on_merge = MergeHook(Some(Box::new(f)));
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn save_to_path(&self, path: &Path) -> io::Result<()> {
+        let json: String = serde_json::to_string_pretty(self)?;
+        return fs::write(path, json);
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn load_from_path(path: &Path) -> Result<Game, Box<dyn Error>> {
+        let json: String = fs::read_to_string(path)?;
+        return Ok(serde_json::from_str(&json)?);
+    }
+
+    // restores a saved game, checked the same way as a freshly generated or given board
+    #[cfg(feature = "serde")]
+    fn from_save(save: SaveFile) -> Result<Game, Box<dyn Error>> {
+        let config: BoardConfig = save.config;
+
+        // same rules as a freshly generated board
+        let capacity: Option<usize> = config.width.checked_mul(config.height);
+        let occupied: Option<usize> = config.count.checked_add(config.blockers);
+        let fits: bool = match (occupied, capacity) {
+            (Some(occupied), Some(capacity)) => occupied < capacity,
+            _ => false
+        };
+        if config.count == 0 || fits == false {
+            return Err(format!("Invalid save: tile count {} doesn't fit a {}x{} board", config.count, config.width, config.height).into());
+        }
+
+        let mut game: Game = Game::from_board(save.board, config, save.score)?;
+        game.moves = save.moves;
+        game.has_won = save.has_won;
+        game.merges_remaining = save.merges_remaining;
+        return Ok(game);
+    }
+
+    // the board & the config it needs as a short url safe code, for sharing puzzles, see `import_code`
+    // version byte, width, height, count, base, target & score as varints, then one exponent byte per cell
+    pub fn export_code(&self) -> String {
+        let mut bytes: Vec<u8> = vec![CODE_VERSION];
+        let config: &BoardConfig = &self.config;
+        for value in [config.width as u64, config.height as u64, config.count as u64, config.base, config.target, self.score] {
+            push_varint(&mut bytes, value);
+        }
+        bytes.extend(self.board.tiles().map(|tile| exponent_byte(tile, config.base)));
+
+        return URL_SAFE_NO_PAD.encode(bytes);
+    }
+
+    // fingerprint of the board alone, the score & everything else are left out, e.g. for transposition tables
+    // same size & tiles give the same hash, in every run & on every platform
+    pub fn board_hash(&self) -> u64 {
+        let size = (self.config.width as u64).to_le_bytes().into_iter().chain((self.config.height as u64).to_le_bytes());
+        let cells = self.board.tiles().map(|tile| exponent_byte(tile, self.config.base));
+
+        return size.chain(cells).fold(FNV_OFFSET, |hash, byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME));
+    }
+
+    // a game from `export_code`, the rest of the config is the default one
+    pub fn import_code(code: &str) -> Result<Game, GameError> {
+        let bytes: Vec<u8> = URL_SAFE_NO_PAD.decode(code.trim()).map_err(|_| GameError::InvalidCode)?;
+        let mut bytes = bytes.into_iter();
+        if bytes.next() != Some(CODE_VERSION) { return Err(GameError::InvalidCode); }
+
+        let mut header: [u64; 6] = [0; 6];
+        for value in header.iter_mut() {
+            *value = read_varint(&mut bytes).ok_or(GameError::InvalidCode)?;
+        }
+        let [width, height, count, base, target, score] = header;
+        let (width, height, count): (usize, usize, usize) = (width as usize, height as usize, count as usize);
+        if width == 0 || height == 0 { return Err(GameError::InvalidDimensions { width, height }); }
+
+        let cells: Vec<u8> = bytes.collect();
+        if width.checked_mul(height) != Some(cells.len()) { return Err(GameError::InvalidCode); }
+        let tiles: Vec<Tile> = cells.iter().map(|&exponent| match exponent {
+            0 => Some(0),
+            CODE_WALL => Some(WALL),
+            exponent => base.checked_pow(exponent as u32)
+        }).collect::<Option<Vec<Tile>>>().ok_or(GameError::InvalidCode)?;
+
+        // same rules as a freshly generated board, resets start from `count` tiles & the same number of walls
+        let blockers: usize = tiles.iter().filter(|&&tile| tile == WALL).count();
+        let capacity: usize = cells.len();
+        let occupied: Option<usize> = count.checked_add(blockers);
+        if count == 0 || occupied.is_none_or(|occupied| occupied >= capacity) {
+            return Err(GameError::Overflow { count: occupied.unwrap_or(usize::MAX), capacity });
+        }
+
+        let config: BoardConfig = BoardConfig { width, height, count, base, target, blockers, ..Default::default() };
+        let board: Vec<Vec<Tile>> = tiles.chunks(width).map(<[Tile]>::to_vec).collect();
+        return Game::from_board(board, config, score);
+    }
+
+    // high scores are kept per board size & target, so a 4x4 best doesn't overwrite an 8x8 best
+    fn high_score_key(&self) -> String {
+        return format!("{}x{}x{}", self.config.width, self.config.height, self.config.target);
+    }
 
-                for j in (i + 1)..self.config.width {
-                    if self.board[row][j] == 0 { continue; }
-                    if self.board[row][i] == self.board[row][j] { return false; }
-                    break;
+    // reads the stored high score for this configuration and shows it beside the score
+    pub fn load_high_score(&mut self) -> u64 {
+        let best: u64 = highscore::load(&self.high_score_key());
+        self.best = Some(best);
+        return best;
+    }
+
+    // stores the current score if it beats the stored high score
+    pub fn save_high_score(&self) -> io::Result<()> {
+        return highscore::save(&self.high_score_key(), self.score);
+    }
+
+    // fewest moves the target was ever reached in with this configuration, `None` if it never was
+    pub fn fewest_moves(&self) -> Option<u32> {
+        return highscore::load_fewest_moves(&self.high_score_key());
+    }
+
+    // stores the moves it took to reach the target if they beat the record, meant for right after `Won`
+    // undone moves still count, undo doesn't take back moves
+    pub fn save_fewest_moves(&self) -> io::Result<()> {
+        return highscore::save_fewest_moves(&self.high_score_key(), self.moves);
+    }
+
+    // no move can change the board anymore, or the merge budget ended the game
+    pub fn is_game_over(&self) -> bool {
+        return self.game_over();
+    }
+
+    // the target has been reached, during play or already on the starting board
+    #[inline(always)]
+    pub fn has_won(&self) -> bool {
+        return self.has_won;
+    }
+
+    fn game_over(&self) -> bool {
+
+        // too slow, and the move timer says that's the end
+        if self.timed_out == true { return true; }
+
+        // won, and the config says that's the end
+        if self.config.stop_at_target == true && self.has_won == true { return true; }
+
+        // out of merges, and the config says that ends the game
+        if self.merges_allowed() == false && self.config.budget_exhausted == BudgetExhausted::GameOver { return true; }
+
+        // a tile next to an empty cell can always slide into it, no need to look for merges
+        // gravity only moves down or sideways, so it needs the full check
+        if self.config.gravity == false && self.has_open_tile() == true { return false; }
+
+        // over once no direction changes the board, merge budget included
+        // free cells alone aren't enough, walls or a board without spawns can leave tiles stuck
+        return DIRECTIONS.into_iter().any(|direction| self.can_move(direction)) == false;
+    }
+
+    // any tile with an empty cell right next to it
+    fn has_open_tile(&self) -> bool {
+        let (width, height): (usize, usize) = (self.config.width, self.config.height);
+        for row in 0..height {
+            for column in 0..width {
+                if self.board[(row, column)] != 0 { continue; }
+
+                let neighbours: [Option<Position>; 4] = [
+                    row.checked_sub(1).map(|up| (up, column)),
+                    (row + 1 < height).then_some((row + 1, column)),
+                    column.checked_sub(1).map(|left| (row, left)),
+                    (column + 1 < width).then_some((row, column + 1))
+                ];
+                for neighbour in neighbours.into_iter().flatten() {
+                    if self.board[neighbour] != 0 && self.board[neighbour] != WALL { return true; }
                 }
             }
         }
 
-        // right move
+        return false;
+    }
+
+    // a move is legal if any tile can slide into an empty cell or merge with its neighbour
+    pub fn can_move_left(&self) -> bool {
+        if self.config.gravity == true { return self.can_rotate(Keypress::Left); }
+
         for row in 0..self.config.height {
-            for i in (0..self.config.width).rev() {
-                if self.board[row][i] == 0 { continue; }
+            for i in 1..self.config.width {
+                if self.board[(row, i)] == 0 || self.board[(row, i)] == WALL { continue; }
+                if self.board[(row, i - 1)] == 0 || (self.board[(row, i - 1)] == self.board[(row, i)] && can_grow(self.board[(row, i)], self.config.base) && self.merges_allowed()) { return true; }
+            }
+        }
 
-                for j in (0..i).rev() {
-                    if self.board[row][j] == 0 { continue; }
-                    if self.board[row][i] == self.board[row][j] { return false; }
-                    break;
-                }
+        return self.can_wrap(false);
+    }
+
+    pub fn can_move_right(&self) -> bool {
+        if self.config.gravity == true { return self.can_rotate(Keypress::Right); }
+
+        for row in 0..self.config.height {
+            for i in 0..self.config.width.saturating_sub(1) {
+                if self.board[(row, i)] == 0 || self.board[(row, i)] == WALL { continue; }
+                if self.board[(row, i + 1)] == 0 || (self.board[(row, i + 1)] == self.board[(row, i)] && can_grow(self.board[(row, i)], self.config.base) && self.merges_allowed()) { return true; }
             }
         }
 
-        // up move
-        for column in 0..self.config.width {
-            for i in 0..self.config.height {
-                if self.board[i][column] == 0 { continue; }
+        return self.can_wrap(false);
+    }
 
-                for j in (i + 1)..self.config.height {
-                    if self.board[j][column] == 0 { continue; }
-                    if self.board[i][column] == self.board[j][column] { return false; }
-                    break;
-                }
+    pub fn can_move_up(&self) -> bool {
+        if self.config.gravity == true { return false; } // nothing falls up
+
+        for column in 0..self.config.width {
+            for i in 1..self.config.height {
+                if self.board[(i, column)] == 0 || self.board[(i, column)] == WALL { continue; }
+                if self.board[(i - 1, column)] == 0 || (self.board[(i - 1, column)] == self.board[(i, column)] && can_grow(self.board[(i, column)], self.config.base) && self.merges_allowed()) { return true; }
             }
         }
 
-        // down move
+        return self.can_wrap(true);
+    }
+
+    pub fn can_move_down(&self) -> bool {
         for column in 0..self.config.width {
-            for i in (0..self.config.height).rev() {
-                if self.board[i][column] == 0 { continue; }
+            for i in 0..self.config.height.saturating_sub(1) {
+                if self.board[(i, column)] == 0 || self.board[(i, column)] == WALL { continue; }
+                if self.board[(i + 1, column)] == 0 || (self.board[(i + 1, column)] == self.board[(i, column)] && can_grow(self.board[(i, column)], self.config.base) && self.merges_allowed()) { return true; }
+            }
+        }
 
-                for j in (0..i).rev() {
-                    if self.board[j][column] == 0 { continue; }
-                    if self.board[i][column] == self.board[j][column] { return false; }
-                    break;
-                }
+        return self.can_wrap(true);
+    }
+
+    // wrap mode, a row (or column) whose first & last tile match can merge them across the edge
+    // walls cut the ring, so lines with walls never wrap
+    fn can_wrap(&self, columns: bool) -> bool {
+        if self.config.wrap == false || self.merges_allowed() == false { return false; }
+
+        let lines: Vec<Vec<Tile>> = if columns { self.board.cols() } else { self.board.rows().to_vec() };
+        for line in lines {
+            if line.contains(&WALL) { continue; }
+
+            let mut tiles = line.iter().filter(|&&tile| tile != 0);
+            let (Some(first), Some(last)) = (tiles.next(), tiles.next_back()) else { continue; };
+            if first == last && can_grow(*first, self.config.base) { return true; }
+        }
+
+        return false;
+    }
+
+    // gravity mode, left & right change the board unless every column is the same and nothing can fall
+    fn can_rotate(&self, direction: Keypress) -> bool {
+        let mut next: Game = self.clone();
+        return next.shift(direction);
+    }
+
+    // bit 0 - left, bit 1 - right, bit 2 - up, bit 3 - down
+    // a set bit means the move would change the board
+    pub fn legal_moves_mask(&self) -> u8 {
+        let mut mask: u8 = 0;
+        if self.can_move_left() { mask |= 1 << 0; }
+        if self.can_move_right() { mask |= 1 << 1; }
+        if self.can_move_up() { mask |= 1 << 2; }
+        if self.can_move_down() { mask |= 1 << 3; }
+        return mask;
+    }
+
+    // directions that would change the board, nothing is moved or spawned
+    pub fn available_moves(&self) -> Vec<Keypress> {
+        return DIRECTIONS.into_iter().filter(|&direction| self.can_move(direction)).collect();
+    }
+
+    fn can_move(&self, direction: Keypress) -> bool {
+        match direction {
+            Keypress::Left => self.can_move_left(),
+            Keypress::Right => self.can_move_right(),
+            Keypress::Up => self.can_move_up(),
+            Keypress::Down => self.can_move_down(),
+            _ => false
+        }
+    }
+
+    // slide & merge only, no tile is spawned
+    // returns whether the board changed
+    fn shift(&mut self, direction: Keypress) -> bool {
+        self.slides.clear();
+        self.merged.clear();
+        match direction {
+            Keypress::Left | Keypress::Right if self.config.gravity == true => self.rotate_columns(direction),
+            Keypress::Up if self.config.gravity == true => false,
+            Keypress::Left => self.move_left(),
+            Keypress::Right => self.move_right(),
+            Keypress::Up => self.move_up(),
+            Keypress::Down => self.move_down(),
+            _ => false
+        }
+    }
+
+    // every legal direction paired with the game right after that move, before a tile spawns
+    pub fn successors(&self) -> Vec<(Keypress, Game)> {
+        let mut successors: Vec<(Keypress, Game)> = vec![];
+        if self.game_over() == true { return successors; }
+
+        for direction in self.available_moves() {
+            let mut next: Game = self.clone();
+            next.shift(direction);
+            successors.push((direction, next));
+        }
+
+        return successors;
+    }
+
+    // the board & score `direction` would leave, before a tile spawns, e.g. for showing a ghost of the move
+    // nothing changes and the rng isn't touched, combo bonuses only count once the move is made
+    pub fn preview(&self, direction: Keypress) -> (Grid, u64) {
+        let mut next: Game = self.clone();
+        next.shift(direction);
+        return (next.board, next.score);
+    }
+
+    // how many tiles `direction` would merge, 0 when it only slides or doesn't move at all
+    pub fn preview_merges(&self, direction: Keypress) -> usize {
+        let mut next: Game = self.clone();
+        next.shift(direction);
+        return next.merged.len();
+    }
+
+    // best looking move by the greedy heuristic, moves that don't change the board are never suggested
+    pub fn suggest_move(&self) -> Option<Keypress> {
+        return auto::greedy(self);
+    }
+
+    // plays `suggest_move` on a copy until it's stuck, returns its moves & final score
+    // the copy starts with the same rng, so the game itself is left exactly as it was
+    pub fn solve(&self) -> (Vec<Keypress>, u64) {
+        let mut solver: Game = self.clone();
+        solver.paused = false;
+        solver.recorder = None;
+
+        let mut moves: Vec<Keypress> = vec![];
+        while moves.len() < SOLVE_MOVE_LIMIT {
+            let Some(keypress) = solver.suggest_move() else { break; };
+            solver.fast_apply(keypress);
+            moves.push(keypress);
+        }
+
+        return (moves, solver.score);
+    }
+
+    // every possible tile spawn paired with its probability, always a single spawn even with `spawn_per_move` above 1
+    pub(crate) fn spawns(&self) -> Vec<(f64, Game)> {
+        if self.config.spawn_on_move == false { return vec![(1.0, self.clone())]; }
+        let free_tiles: Vec<Position> = self.empty_positions();
+
+        let mut spawns: Vec<(f64, Game)> = Vec::with_capacity(free_tiles.len() * 2);
+        let position_chance: f64 = 1.0 / free_tiles.len() as f64;
+        for position in free_tiles {
+            for (value, chance) in spawn_distribution(&self.config, self.largest_tile()) {
+                let mut next: Game = self.clone();
+                next.board[position] = value;
+                spawns.push((position_chance * chance, next));
             }
         }
 
-        return true;
+        return spawns;
+    }
+
+    pub fn moves(&self) -> u32 {
+        return self.moves;
+    }
+
+    // total score after each successful move, oldest first
+    pub fn score_history(&self) -> &[u64] {
+        return &self.score_history;
+    }
+
+    // `move,score,largest_tile` header, then one line per successful move, for graphing outside the game
+    pub fn stats_csv(&self) -> String {
+        let mut csv: String = String::from("move,score,largest_tile\n");
+        for (index, (score, tile)) in self.score_history.iter().zip(&self.tile_history).enumerate() {
+            csv.push_str(&format!("{},{},{}\n", index + 1, score, tile));
+        }
+        return csv;
+    }
+
+    // wall-clock time since the game started
+    pub fn elapsed(&self) -> Duration {
+        return self.started_at.elapsed();
+    }
+
+    // informational board health, see `auto::quality`, higher is better
+    pub fn quality(&self) -> f64 {
+        return auto::quality(self.board.rows());
+    }
+
+    // merging moves in a row, always 0 without combo scoring
+    pub fn streak(&self) -> u32 {
+        return self.streak;
+    }
+
+    // tile spawned by the last move & its value, `None` when nothing spawned or the move was undone
+    pub fn last_spawn(&self) -> Option<(Position, Tile)> {
+        return self.last_spawn.map(|position| (position, self.board[position]));
+    }
+
+    pub fn is_paused(&self) -> bool {
+        return self.paused;
+    }
+
+    // time until a timed game ends, `None` without a time limit
+    pub fn time_left(&self) -> Option<Duration> {
+        return self.config.time_limit.map(|limit| limit.saturating_sub(self.elapsed()));
+    }
+
+    pub fn time_up(&self) -> bool {
+        return self.time_left() == Some(Duration::ZERO);
+    }
+
+    // time the current move has, `decay` shrinks it with every successful move, `None` without a move timer
+    pub fn move_time(&self) -> Option<Duration> {
+        let timer: MoveTimer = self.config.move_timer?;
+        let time: Duration = timer.start.mul_f64(timer.decay.powf(self.moves as f64));
+        return Some(time.max(timer.floor));
+    }
+
+    // time left for the current move, `None` without a move timer & while it stands still, paused or in the sandbox
+    pub fn move_time_left(&self) -> Option<Duration> {
+        if self.paused == true || self.sandbox == true { return None; }
+        return self.move_time().map(|time| time.saturating_sub(self.move_started_at.elapsed()));
+    }
+
+    // the penalty for a move that didn't come in time, meant for once `move_time_left` reaches zero
+    // `TimedOut` with the penalty tile, `GameOver` when the penalty ends the game, `NoMove` without a move timer
+    pub fn move_timeout(&mut self) -> GameResult {
+        let Some(timer) = self.config.move_timer else { return GameResult::NoMove; };
+        if self.game_over() == true { return GameResult::GameOver; }
+
+        let spawned: Option<(Position, Tile)> = match timer.penalty {
+            TimeoutPenalty::Spawn => self.add_random_tile(),
+            TimeoutPenalty::GameOver => None
+        };
+        let Some(spawned) = spawned else {
+            self.timed_out = true;
+            return GameResult::GameOver;
+        };
+
+        self.slides.clear();
+        self.merged.clear();
+        self.last_spawn = Some(spawned.0);
+        self.move_started_at = Instant::now();
+        return GameResult::TimedOut { spawned };
+    }
+
+    // highest tile on the board, 0 on an empty board, walls don't count
+    pub fn largest_tile(&self) -> Tile {
+        return self.board.tiles().filter(|&tile| tile != WALL).max().unwrap_or(0);
+    }
+
+    // a move to take back, a game the move timer ended stays over
+    pub fn can_undo(&self) -> bool {
+        return self.history.is_empty() == false && self.timed_out == false;
+    }
+
+    pub fn empty_count(&self) -> usize {
+        return self.board.tiles().filter(|&tile| tile == 0).count();
+    }
+
+    pub fn is_full(&self) -> bool {
+        return self.empty_count() == 0;
+    }
+
+    // no empty cell left, moves may still remain, see `is_game_over`
+    pub fn is_board_full(&self) -> bool {
+        return self.is_full();
+    }
+
+    #[inline(always)]
+    pub fn board(&self) -> &[Vec<Tile>] {
+        return self.board.rows();
+    }
+
+    // chance that the next spawned tile is `base * base` instead of `base`
+    pub fn four_chance(&self) -> f64 {
+        return four_chance(&self.config, self.largest_tile());
+    }
+
+    #[inline(always)]
+    pub fn grid(&self) -> &Grid {
+        return &self.board;
+    }
+
+    #[inline(always)]
+    pub fn score(&self) -> u64 {
+        return self.score;
+    }
+
+    // the score reached `Tile::MAX` and doesn't count any higher
+    pub fn score_capped(&self) -> bool {
+        return self.score == u64::MAX;
+    }
+
+    // `None` when the coordinates are outside of the board
+    pub fn tile_at(&self, row: usize, col: usize) -> Option<Tile> {
+        return self.board.get(row, col);
     }
 
     pub fn play_move(&mut self, getch: &Getch) -> Result<GameResult, Box<dyn std::error::Error>> {
 
         // game over check
         if self.game_over() == true { return Ok(GameResult::GameOver); }
+        if self.time_up() == true { return Ok(GameResult::TimeUp); }
 
         // user input
         let input: Key = getch.getch()?;
-        let keypress: Keypress = match Keypress::try_from(input) {
+        return Ok(self.play_key(input));
+    }
+
+    // `play_move` with an already read key, for input that doesn't come straight from `Getch`
+    pub fn play_key(&mut self, input: Key) -> GameResult {
+
+        // the clock kept running while waiting for the key
+        if self.time_up() == true { return GameResult::TimeUp; }
+
+        // any key but `y` cancels a pending reset, and does nothing else
+        if self.reset_pending == true {
+            self.reset_pending = false;
+            if input != Key::Char('y') && input != Key::Char('Y') { return GameResult::NoMove; }
+            if let Some(recorder) = self.recorder.as_mut() {
+                recorder.record(Keypress::Reset);
+            }
+            return GameResult::Reset;
+        }
+
+        let keypress: Keypress = match self.key_bindings.resolve(input) {
             Ok(key) => key,
-            Err(_) => return Ok(GameResult::UnknownKeyPress)
+            Err(_) => return GameResult::UnknownKeyPress
         };
 
-        // used to check if the move was `successful`, eliminating reduntant moves
-        let board_before_move: Board = self.board.clone();
+        // an unconfirmed reset isn't recorded, it's only recorded once it goes through
+        let asking: bool = keypress == Keypress::Reset && self.confirm_reset == true;
+        if let (Some(recorder), false) = (self.recorder.as_mut(), asking) {
+            recorder.record(keypress);
+        }
 
-        match keypress {
-            Keypress::Left => self.move_left(),
-            Keypress::Right => self.move_right(),
-            Keypress::Up => self.move_up(),
-            Keypress::Down => self.move_down(),
-            Keypress::Quit => return Ok(GameResult::Exit),
-            Keypress::Reset => return Ok(GameResult::Reset)
+        return self.apply_move(keypress);
+    }
+
+    // headless counterpart of `play_move`, takes the keypress directly instead of reading it
+    pub fn apply_move(&mut self, keypress: Keypress) -> GameResult {
+
+        // the sandbox works on any board, even one that's stuck
+        if keypress == Keypress::Sandbox && self.paused == false { return self.toggle_sandbox(); }
+        if self.sandbox == true && matches!(keypress, Keypress::Reset | Keypress::Quit | Keypress::Save) == false { return self.edit(keypress); }
+
+        // the move that lost the game can still be undone, it may have been a slip
+        if keypress == Keypress::Undo && self.paused == false { return self.undo(); }
+
+        // game over check
+        if self.game_over() == true { return GameResult::GameOver; }
+
+        if keypress == Keypress::Pause {
+            self.paused = !self.paused;
+            self.move_started_at = Instant::now(); // the move timer stood still while paused
+            return if self.paused { GameResult::Paused } else { GameResult::Resumed };
         }
+        if self.paused == true && matches!(keypress, Keypress::Reset | Keypress::Quit) == false { return GameResult::Paused; }
+
+        let score_before_move: u64 = self.score;
+        let merges_before_move: Option<u32> = self.merges_remaining;
+        let empty_before_move: usize = self.empty_count();
+
+        let moved: bool = match keypress {
+            Keypress::Quit => return GameResult::Exit,
+            Keypress::Reset if self.confirm_reset == true => {
+                self.reset_pending = true;
+                return GameResult::ConfirmReset;
+            },
+            Keypress::Reset => return GameResult::Reset,
+            Keypress::Save => return GameResult::Save,
+            Keypress::Undo => return self.undo(),
+            Keypress::Hint => return self.suggest_move().map_or(GameResult::NoMove, GameResult::Hint),
+            Keypress::Solve => {
+                let (moves, score) = self.solve();
+                return GameResult::Solution { moves, score };
+            },
+            Keypress::Wider | Keypress::Narrower | Keypress::Taller | Keypress::Shorter => return self.resize_step(keypress),
+            Keypress::Transpose => match self.transpose() {
+                Ok(()) => return GameResult::Transposed,
+                Err(_) => return GameResult::NoMove
+            },
+            Keypress::Sandbox | Keypress::Place(_) | Keypress::Skip => return GameResult::NoMove,
+            direction => self.shift(direction)
+        };
 
-        if equal_boards(&self.board, &board_before_move) == false {
-            // move made, add random tile
-            self.add_random_tile();
-        } else {
-            return Ok(GameResult::NoMove);
+        // eliminating reduntant moves
+        if moved == false { return GameResult::NoMove; }
+
+        // every merge frees exactly one cell
+        let merges: u32 = (self.empty_count() - empty_before_move) as u32;
+        let largest_merge: Tile = self.merged.iter().map(|&(_, value)| value).max().unwrap_or(0);
+
+        // move made, remember the old board and add random tile
+        self.remember(score_before_move, merges_before_move);
+        return self.finish_move(score_before_move, merges, largest_merge);
+    }
+
+    // same as `apply_move` on a packed board, for simulations that play a lot of moves
+    // only classic 4x4 boards can be packed, anything else goes through `apply_move`
+    // the tiles don't slide in `animate_last_move` after a packed move
+    pub fn fast_apply(&mut self, keypress: Keypress) -> GameResult {
+        let packed: Option<BitBoard> = if self.paused == false && self.sandbox == false { self.bitboard() } else { None };
+        let Some(before) = packed else { return self.apply_move(keypress); };
+        if DIRECTIONS.contains(&keypress) == false { return self.apply_move(keypress); }
+
+        // game over check, no budget or walls on a packable board so it's just the four directions
+        if before.is_stuck() { return GameResult::GameOver; }
+
+        let (after, gained, largest_merge): (BitBoard, u64, Tile) = before.shift_merging(keypress);
+        if after == before { return GameResult::NoMove; }
+
+        // the board is only copied for undo, otherwise the move happens in place
+        let score_before_move: u64 = self.score;
+        if self.keeps_history() == true {
+            self.push_history(self.board.clone(), score_before_move, self.merges_remaining); // no budget on a packed board
+        }
+        let merges: u32 = (after.empty_count() - before.empty_count()) as u32;
+        after.write_to(&mut self.board);
+        self.slides.clear();
+        self.merged.clear();
+        self.apply_score(gained);
+        return self.finish_move(score_before_move, merges, largest_merge);
+    }
+
+    // the board packed for searches that play a lot of moves, `None` unless the game plays by the classic 4x4 rules
+    // `BitBoard::shift` on it gives the same board & score as a move of this game
+    pub fn bitboard(&self) -> Option<BitBoard> {
+        if self.packable() == false { return None; }
+        return BitBoard::from_grid(&self.board);
+    }
+
+    // rules the packed board plays by, everything that changes how lines collapse rules it out
+    fn packable(&self) -> bool {
+        return self.config.width == bitboard::SIZE && self.config.height == bitboard::SIZE
+            && self.config.base == 2
+            && self.config.merge_budget.is_none()
+            && self.config.merge_resolution != MergeResolution::LastWins // competing pairs are equal, so highest value is first wins
+            && self.config.gravity == false
+            && self.config.wrap == false
+            && self.config.stop_at_target == false // the packed game over check only looks at the directions
+            && self.timed_out == false // same, a game the move timer ended can still have moves left
+            && self.on_merge.0.is_none();
+    }
+
+    // everything after the board changed, shared by `apply_move` & `fast_apply`
+    fn finish_move(&mut self, score_before_move: u64, merges: u32, largest_merge: Tile) -> GameResult {
+        self.score_streak(self.score - score_before_move);
+        let summary: MoveSummary = MoveSummary { merges, points: self.score - score_before_move, largest_merge };
+        let spawns: usize = if self.config.spawn_on_move { self.config.spawn_per_move } else { 0 };
+        let spawned: Option<(Position, Tile)> = self.add_random_tiles(spawns);
+        self.last_spawn = spawned.map(|(position, _)| position);
+        self.moves += 1;
+        self.move_started_at = Instant::now();
+        self.score_history.push(self.score);
+        self.tile_history.push(self.largest_tile());
+
+        // first time reaching the target
+        if self.has_won == false && self.largest_tile() >= self.config.target {
+            self.has_won = true;
+            return if self.config.stop_at_target { GameResult::GameOver } else { GameResult::Won };
         }
 
-        return Ok(GameResult::NextMove);
+        return GameResult::NextMove { summary, spawned };
+    }
+
+    // keeps the board from before the last move for undo, rebuilt from the slides so only moves that changed something pay for it
+    fn remember(&mut self, score: u64, merges_remaining: Option<u32>) {
+        if self.keeps_history() == false { return; }
+
+        let mut board: Grid = Grid::new(self.config.width, self.config.height);
+        for &(from, _, value) in &self.slides {
+            board[from] = value;
+        }
+        self.push_history(board, score, merges_remaining);
     }
 
-    fn add_random_tile(&mut self) {
+    // `board`, `score` & `merges_remaining` from before the move, the rest only changes once the move is finished
+    fn push_history(&mut self, board: Grid, score: u64, merges_remaining: Option<u32>) {
+        if self.keeps_history() == false { return; }
+
+        if self.history.len() == self.config.undo_depth {
+            self.history.remove(0);
+        }
+        self.history.push(Snapshot { board, score, moves: self.moves, has_won: self.has_won, merges_remaining });
+    }
+
+    fn keeps_history(&self) -> bool {
+        return self.config.undo_depth > 0 && self.config.mode.allows_undo() == true;
+    }
+
+    // grows or shrinks the board, tiles keep their place counted from the top left corner, anything past the new edges is gone
+    // the undo history is dropped since the old boards don't fit anymore, a board left without tiles gets a new one
+    pub fn resize(&mut self, width: usize, height: usize) -> Result<(), GameError> {
+        if width == 0 || height == 0 { return Err(GameError::InvalidDimensions { width, height }); }
+        let capacity: usize = width * height;
+        let occupied: usize = self.config.count + self.config.blockers;
+        // same rules as `random_board`, so a reset on the new size still deals a board
+        if occupied == capacity { return Err(GameError::FullBoard); }
+        if occupied > capacity { return Err(GameError::Overflow { count: occupied, capacity }); }
+
+        let mut board: Grid = Grid::new(width, height);
+        for row in 0..height.min(self.config.height) {
+            for column in 0..width.min(self.config.width) {
+                board[(row, column)] = self.board[(row, column)];
+            }
+        }
+
+        self.config.width = width;
+        self.config.height = height;
+        self.board = board;
+        self.history.clear();
+        self.slides.clear();
+        self.merged.clear();
+        self.last_spawn = None;
+
+        if self.board.tiles().all(|tile| tile == 0 || tile == WALL) {
+            self.last_spawn = self.add_random_tile().map(|(position, _)| position);
+        }
+        return Ok(());
+    }
+
+    // sandbox mode edits the board instead of playing it, leaving it plays on from the edited board
+    fn toggle_sandbox(&mut self) -> GameResult {
+        self.sandbox = !self.sandbox;
+        self.slides.clear();
+        self.merged.clear();
+        self.last_spawn = None;
+        if self.sandbox == true {
+            // the board could have shrunk since the cursor was last used
+            self.cursor = (self.cursor.0.min(self.config.height - 1), self.cursor.1.min(self.config.width - 1));
+            return GameResult::SandboxEntered;
+        }
+
+        // undo can't go back into the sandbox, & a placed tile can reach the target without winning
+        self.history.clear();
+        self.has_won = self.board.tiles().any(|tile| tile != WALL && tile >= self.config.target);
+        if self.board.tiles().all(|tile| tile == 0 || tile == WALL) {
+            self.last_spawn = self.add_random_tile().map(|(position, _)| position);
+        }
+        self.move_started_at = Instant::now();
+        return GameResult::SandboxLeft;
+    }
+
+    // a sandbox key, the directions move the cursor & stop at the edges, `Place` sets the tile under it
+    fn edit(&mut self, keypress: Keypress) -> GameResult {
+        let (row, column): Position = self.cursor;
+        self.cursor = match keypress {
+            Keypress::Up => (row.saturating_sub(1), column),
+            Keypress::Down => ((row + 1).min(self.config.height - 1), column),
+            Keypress::Left => (row, column.saturating_sub(1)),
+            Keypress::Right => (row, (column + 1).min(self.config.width - 1)),
+            Keypress::Place(exponent) => {
+                let tile: Tile = if exponent == 0 { 0 } else { self.config.base.saturating_pow(exponent as u32) };
+                self.board[self.cursor] = tile;
+                self.cursor
+            },
+            _ => return GameResult::NoMove
+        };
+        return GameResult::Edited;
+    }
+
+    #[inline(always)]
+    pub fn is_sandbox(&self) -> bool {
+        return self.sandbox;
+    }
+
+    // cell the sandbox keys edit, `None` outside of the sandbox
+    pub fn cursor(&self) -> Option<Position> {
+        return if self.sandbox { Some(self.cursor) } else { None };
+    }
+
+    // swaps rows & columns in place, a left move afterwards has to match an up move before
+    // only square boards, anything else would change the board size
+    pub fn transpose(&mut self) -> Result<(), GameError> {
+        if self.config.width != self.config.height {
+            return Err(GameError::InvalidDimensions { width: self.config.width, height: self.config.height });
+        }
+
+        self.board = Grid::from(self.board.cols());
+        self.last_spawn = self.last_spawn.map(|(row, column)| (column, row));
+        self.slides.clear();
+        self.merged.clear();
+        return Ok(());
+    }
+
+    // one column or row more or less, the board stays as it is when it can't get that small
+    fn resize_step(&mut self, keypress: Keypress) -> GameResult {
+        let (width, height): (usize, usize) = match keypress {
+            Keypress::Wider => (self.config.width + 1, self.config.height),
+            Keypress::Narrower => (self.config.width - 1, self.config.height),
+            Keypress::Taller => (self.config.width, self.config.height + 1),
+            Keypress::Shorter => (self.config.width, self.config.height - 1),
+            _ => return GameResult::NoMove
+        };
+
+        match self.resize(width, height) {
+            Ok(()) => return GameResult::Resized,
+            Err(_) => return GameResult::NoMove
+        }
+    }
+
+    // restores the board & score from before the last move
+    fn undo(&mut self) -> GameResult {
+        if self.can_undo() == false { return GameResult::NoMove; }
+
+        match self.history.pop() {
+            Some(snapshot) => {
+                self.board = snapshot.board;
+                self.score = snapshot.score;
+                self.moves = snapshot.moves;
+                self.has_won = snapshot.has_won;
+                self.merges_remaining = snapshot.merges_remaining;
+                self.streak = 0; // undoing breaks the combo
+                self.last_spawn = None;
+                return GameResult::Undone;
+            },
+            None => return GameResult::NoMove
+        }
+    }
+
+    /// All empty cells, in row-major order.
+    ///
+    /// ```
+    /// use game_2048::game::{BoardConfig, Game};
+    ///
+    /// let config: BoardConfig = BoardConfig { width: 2, height: 2, count: 1, ..Default::default() };
+    /// let game: Game = Game::from_board(vec![vec![2, 0], vec![0, 4]], config, 0).unwrap();
+    /// assert_eq!(game.empty_positions(), [(0, 1), (1, 0)]);
+    /// ```
+    pub fn empty_positions(&self) -> Vec<Position> {
         let mut free_tiles: Vec<Position> = vec![];
 
         for i in 0..self.config.height {
             for j in 0..self.config.width {
-                if self.board[i][j] == 0 {
+                if self.board[(i, j)] == 0 {
                     free_tiles.push((i, j));
                 }
             }
         }
 
-        if free_tiles.len() == 0 { return; } // no free tiles
+        return free_tiles;
+    }
+
+    // returns where the tile spawned & its value
+    // up to `count` random tiles, stops early once the board is full, returns the first one
+    fn add_random_tiles(&mut self, count: usize) -> Option<(Position, Tile)> {
+        let mut first: Option<(Position, Tile)> = None;
+        for _ in 0..count {
+            let Some(spawned) = self.add_random_tile() else { break; };
+            first = first.or(Some(spawned));
+        }
+        return first;
+    }
+
+    fn add_random_tile(&mut self) -> Option<(Position, Tile)> {
+        let free_tiles: Vec<Position> = self.empty_positions();
+
+        if free_tiles.is_empty() { return None; } // no free tiles
 
         // pick & apply random position
-        let random_index: usize = thread_rng().gen_range(0..free_tiles.len());
+        let random_index: usize = self.rng.gen_range(0..free_tiles.len());
         let random_position: Position = free_tiles[random_index];
-        self.board[random_position.0][random_position.1] = random_tile();
+        let value: Tile = spawn_tile(&self.config, self.largest_tile(), &mut self.rng);
+        self.board[random_position] = value;
+        return Some((random_position, value));
     }
 
-    fn move_left(&mut self) {
+    // collapses one line towards index 0, applies the score and spends the merge budget
+    // `position` maps an index in the line back to the board
+    fn collapse(&mut self, line: &[Tile], position: impl Fn(usize) -> Position) -> Vec<Tile> {
+        let collapse: Collapse = collapse_line(line, self.config.base, self.config.merge_resolution, self.merges_remaining, self.config.wrap);
+        for &(from, to) in &collapse.slides {
+            self.slides.push((position(from), position(to), line[from]));
+        }
+        self.apply_score(collapse.score);
+        self.spend_merges(collapse.merges.len() as u32);
+
+        self.merged.extend(collapse.merges.iter().map(|&(index, value)| (position(index), value)));
+        if let Some(on_merge) = self.on_merge.0.as_mut() {
+            for &(index, value) in &collapse.merges {
+                on_merge(position(index), value);
+            }
+        }
+
+        return collapse.line;
+    }
+
+    // every move returns whether any line changed
+    fn move_left(&mut self) -> bool {
         // merge from left to right for each row
+        let mut moved: bool = false;
         for row in 0..self.config.height {
-            for i in 0..self.config.width {
-                if self.board[row][i] == 0 { continue; } // try next, skip zeroes
+            let line: Vec<Tile> = self.board.row(row);
+            let collapsed: Vec<Tile> = self.collapse(&line, |i| (row, i));
+            moved |= collapsed != line;
+            self.board.set_row(row, collapsed);
+        }
+
+        return moved;
+    }
 
-                for j in (i + 1)..self.config.width {
-                    if self.board[row][j] == 0 { continue; } // try next, skip zeroes
+    fn move_right(&mut self) -> bool {
+        // merge from right to left for each row, same as a left move on the reversed row
+        let width: usize = self.config.width;
+        let mut moved: bool = false;
+        for row in 0..self.config.height {
+            let line: Vec<Tile> = self.board.row(row).into_iter().rev().collect();
+            let mut collapsed: Vec<Tile> = self.collapse(&line, |i| (row, width - 1 - i));
+            moved |= collapsed != line;
+            collapsed.reverse();
+            self.board.set_row(row, collapsed);
+        }
 
-                    // if the first match can be merged, then merge
-                    if self.board[row][i] == self.board[row][j] {
-                        self.board[row][i] <<= 1;
-                        self.apply_score(self.board[row][i]);
-                        self.board[row][j] = 0;
-                    }
+        return moved;
+    }
 
-                    break;
-                }
-            }
+    fn move_up(&mut self) -> bool {
+        // merge from top to bottom for each column
+        let mut moved: bool = false;
+        for column in 0..self.config.width {
+            let line: Vec<Tile> = self.board.column(column);
+            let collapsed: Vec<Tile> = self.collapse(&line, |i| (i, column));
+            moved |= collapsed != line;
+            self.board.set_column(column, &collapsed);
         }
 
-        // move all zeros to the end of each row
-        self.board.iter_mut().for_each(|row| move_zeroes_end(row));
+        return moved;
     }
 
-    fn move_right(&mut self) {
-        // merge from right to left for each row
-        for row in 0..self.config.height {
-            for i in (0..self.config.width).rev() {
-                if self.board[row][i] == 0 { continue; }
+    fn move_down(&mut self) -> bool {
+        // merge from bottom to top of each column
+        let height: usize = self.config.height;
+        let mut moved: bool = false;
+        for column in 0..self.config.width {
+            let line: Vec<Tile> = self.board.column(column).into_iter().rev().collect();
+            let mut collapsed: Vec<Tile> = self.collapse(&line, |i| (height - 1 - i, column));
+            moved |= collapsed != line;
+            collapsed.reverse();
+            self.board.set_column(column, &collapsed);
+        }
 
-                for j in (0..i).rev() {
-                    if self.board[row][j] == 0 { continue; }
+        return moved;
+    }
 
-                    if self.board[row][i] == self.board[row][j] {
-                        self.board[row][i] <<= 1;
-                        self.apply_score(self.board[row][i]);
-                        self.board[row][j] = 0;
-                    }
+    // gravity mode, every column moves one step to the side, the one on the edge wraps around, then the tiles fall down
+    fn rotate_columns(&mut self, direction: Keypress) -> bool {
+        let width: usize = self.config.width;
+        // column that ends up at `column` after the rotation
+        let source = |column: usize| if direction == Keypress::Left { (column + 1) % width } else { (column + width - 1) % width };
+
+        // identical columns don't change anything by swapping places
+        let columns: Vec<Vec<Tile>> = self.board.cols();
+        let rotated: bool = (0..width).any(|column| columns[source(column)] != columns[column]);
+        for column in 0..width {
+            self.board.set_column(column, &columns[source(column)]);
+        }
+        let fell: bool = self.move_down();
 
-                    break;
-                }
-            }
+        // the tiles slide from where they were before the rotation
+        for slide in self.slides.iter_mut() {
+            slide.0.1 = source(slide.0.1);
         }
 
-        // move all zeros to the beggining of each row
-        self.board.iter_mut().for_each(|row| move_zeroes_start(row));
+        return rotated || fell;
     }
 
-    fn move_up(&mut self) {
-        // merge from top to bottom for each column
-        for column in 0..self.config.width {
-            for i in 0..self.config.height {
-                if self.board[i][column] == 0 { continue; }
 
-                for j in (i + 1)..self.config.height {
-                    if self.board[j][column] == 0 { continue; }
+    // the board as a table, colored unless the render options ask for plain ascii or no colors
+    pub(crate) fn table(&self) -> Table {
+        return self.table_of(&self.board, &[], self.last_spawn);
+    }
 
-                    if self.board[i][column] == self.board[j][column] {
-                        self.board[i][column] <<= 1;
-                        self.apply_score(self.board[i][column]);
-                        self.board[j][column] = 0;
-                    }
+    // any board of this game's size drawn as a table, used for the animation frames too
+    // `flashing` cells get the merge flash colors instead of their own, the `spawned` cell is bold & underlined
+    fn table_of(&self, board: &Grid, flashing: &[Position], spawned: Option<Position>) -> Table {
+        let mut table: Table = Table::new();
+        if self.render.ascii {
+            table.load_preset(ASCII_FULL);
+        } else {
+            table.load_preset(UTF8_FULL).apply_modifier(UTF8_ROUND_CORNERS);
+        }
+        // no fixed width, cells always grow to fit their whole number
 
-                    break;
+        // cell text, padded afterwards so every cell has the same display width
+        let mut labels: Vec<Vec<String>> = board.rows().iter().map(|row| {
+            row.iter().map(|&tile_value| {
+                let mut cell_value: String = String::from("");
+                if tile_value == WALL {
+                    cell_value.push_str(if self.render.ascii { "#" } else { "■" });
+                } else if let Some(glyph) = self.glyphs.glyph(tile_value) {
+                    cell_value.push_str(glyph);
+                } else if tile_value != 0 && self.render.exponent == true {
+                    cell_value.push_str(&tile_value.ilog(self.config.base).to_string());
+                } else if tile_value != 0 {
+                    cell_value.push_str(&tile_value.to_string());
+                } else if let Some(empty) = self.render.empty {
+                    cell_value.push(empty);
                 }
-            }
+                cell_value
+            }).collect()
+        }).collect();
+        // the sandbox cursor is bracketed, so it shows without colors too
+        if let Some((row, column)) = self.cursor() {
+            labels[row][column] = format!("[{}]", labels[row][column]);
         }
+        let cell_width: usize = labels.iter().flatten().map(|label| label.width()).max().unwrap_or(0);
 
-        // move all zeros to the bottom of each column
-        for column in 0..self.config.width {
-            let mut j: usize = 0;
-            for i in 0..self.config.height {
-                if self.board[i][column] != 0 {
-                    (self.board[i][column], self.board[j][column]) = (self.board[j][column], self.board[i][column]);
-                    j += 1;
+        for i in 0..self.config.height {
+
+            let mut row: Vec<Cell> = vec![];
+            for j in 0..self.config.width {
+                let tile_value: Tile = board[(i, j)];
+
+                let cell_colors: (Color, Color) = if flashing.contains(&(i, j)) { MERGE_FLASH_COLORS } else { tile_style(tile_value, self.render.theme) };
+
+                let mut cell: Cell = Cell::new(pad_to_width(&labels[i][j], cell_width))
+                    .set_alignment(CellAlignment::Center);
+                if self.render.colored() == true {
+                    cell = cell.fg(cell_colors.0).bg(cell_colors.1);
+                    if spawned == Some((i, j)) {
+                        cell = cell.add_attributes(SPAWN_ATTRIBUTES.to_vec());
+                    }
+                    if tile_value == 0 {
+                        cell = cell.add_attribute(Attribute::Dim);
+                    }
                 }
+
+                row.push(cell);
             }
+
+            table.add_row(row);
         }
+
+        return table;
     }
 
-    fn move_down(&mut self) {
-        // merge from bottom to top of each column
-        for column in 0..self.config.width {
-            for i in (0..self.config.height).rev() {
-                if self.board[i][column] == 0 { continue; }
+    // slides the tiles of the last move from where they started to where they ended, then flashes the merged tiles
+    // a few in-between frames are drawn before the real board, new tiles only show up on the real board
+    pub fn animate_last_move(&self) {
+        self.slide_tiles();
+        self.flash_merges();
+    }
 
-                for j in (0..i).rev() {
-                    if self.board[j][column] == 0 { continue; }
+    fn slide_tiles(&self) {
+        let frames: usize = self.render.animate_frames;
+        if frames == 0 || self.render.json == true || self.slides.is_empty() { return; }
 
-                    if self.board[i][column] == self.board[j][column] {
-                        self.board[i][column] <<= 1;
-                        self.apply_score(self.board[i][column]);
-                        self.board[j][column] = 0;
-                    }
+        for frame in 1..=frames {
+            // walls are in the slides as well, staying put unless gravity rotates them
+            let mut board: Grid = Grid::new(self.config.width, self.config.height);
 
-                    break;
-                }
+            // `frame / (frames + 1)` of the way, so neither the old nor the new board is repeated
+            for &((from_row, from_column), (to_row, to_column), value) in &self.slides {
+                let step = |from: usize, to: usize| (from as f64 + (to as f64 - from as f64) * frame as f64 / (frames + 1) as f64).round() as usize;
+                let (row, column) = (step(from_row, to_row), step(from_column, to_column));
+                board[(row, column)] = board[(row, column)].max(value);
             }
-        }
 
-        // move all zeros to the top of each column
-        for column in 0..self.config.width {
-            let mut j: usize = self.config.height - 1;
-            for i in (0..self.config.height).rev() {
-                if self.board[i][column] != 0 {
-                    (self.board[i][column], self.board[j][column]) = (self.board[j][column], self.board[i][column]);
-                    j -= 1;
-                }
-            }
+            self.render.clear_screen();
+            self.render.print_controls(&self.key_bindings);
+            println!("{}", self.table_of(&board, &[], None));
+            std::thread::sleep(self.render.frame_delay);
         }
     }
 
+    // one frame of the board with the merged tiles of the last move highlighted
+    fn flash_merges(&self) {
+        let Some(duration) = self.render.merge_flash else { return; };
+        if self.render.json == true || self.render.colored() == false || self.merged.is_empty() { return; }
 
-    // todo add score / stuff
+        self.render.clear_screen();
+        self.render.print_controls(&self.key_bindings);
+        let flashing: Vec<Position> = self.merged.iter().map(|&(position, _)| position).collect();
+        println!("{}", self.table_of(&self.board, &flashing, self.last_spawn));
+        std::thread::sleep(duration);
+    }
+
+    // clears the screen, prints the controls and then the board, or just a json line in json mode
     pub fn display_game(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.render.json == true { return self.print_state(); }
 
-        let mut table: Table = Table::new();
-        table
-            .load_preset(UTF8_FULL)
-            .apply_modifier(UTF8_ROUND_CORNERS)
-            .set_width(100);
+        self.render.clear_screen();
+        self.render.print_controls(&self.key_bindings);
+        print!("{}", self);
 
-        for i in 0..self.config.height {
+        return Ok(());
+    }
 
-            let mut row: Vec<Cell> = vec![];
-            for j in 0..self.config.width {
-                let tile_value: u32 = self.board[i][j];
+    #[cfg(feature = "serde")]
+    fn print_state(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let state: StateDump = StateDump {
+            board: &self.board,
+            score: self.score,
+            moves: self.moves,
+            over: self.game_over() || self.time_up(),
+            last_spawn: self.last_spawn().map(|((row, col), value)| SpawnDump { row, col, value }),
+            four_chance: self.four_chance()
+        };
+        println!("{}", serde_json::to_string(&state)?);
+        return Ok(());
+    }
 
-                let cell_colors: &(Color, Color) = TILE_COLORS.get(&tile_value).unwrap_or(&(Color::White, Color::Black));
+    #[cfg(not(feature = "serde"))]
+    fn print_state(&self) -> Result<(), Box<dyn std::error::Error>> {
+        return Err("json output needs the `serde` feature".into());
+    }
+}
 
-                let mut cell_value: String = String::from("");
-                if tile_value != 0 {
-                    cell_value.push_str(&tile_value.to_string());
-                }
+// a game serializes to its save file, everything else starts fresh when it's read back
+#[cfg(feature = "serde")]
+impl Serialize for Game {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let save: SaveFile = SaveFile {
+            config: self.config.clone(),
+            board: self.board.clone(),
+            score: self.score,
+            moves: self.moves,
+            has_won: self.has_won,
+            merges_remaining: self.merges_remaining
+        };
+        return save.serialize(serializer);
+    }
+}
 
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Game {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let save: SaveFile = SaveFile::deserialize(deserializer)?;
+        return Game::from_save(save).map_err(serde::de::Error::custom);
+    }
+}
 
-                let cell: Cell = Cell::new(cell_value)
-                    .set_alignment(CellAlignment::Center)
-                    .fg(cell_colors.0)
-                    .bg(cell_colors.1);
+// arrow of every direction, green when it would move the board, dimmed when it wouldn't
+// without colors a dead direction is a dot instead, so the line still tells them apart
+// a live arrow is followed by how many merges it would make, e.g. "←2 →0 ↑1 ↓3"
+fn move_arrows(game: &Game) -> String {
+    let render: &RenderOptions = &game.render;
+    let legal: Vec<Keypress> = game.available_moves();
+    let arrows: Vec<String> = DIRECTIONS.into_iter().map(|direction| {
+        let arrow: &str = match (direction, render.ascii) {
+            (Keypress::Left, false) => "←",
+            (Keypress::Right, false) => "→",
+            (Keypress::Up, false) => "↑",
+            (Keypress::Left, true) => "<",
+            (Keypress::Right, true) => ">",
+            (Keypress::Up, true) => "^",
+            (_, false) => "↓",
+            (_, true) => "v"
+        };
 
-                row.push(cell);
-            }
+        if legal.contains(&direction) { return render.paint(format!("{}{}", arrow, game.preview_merges(direction)), Style::new().green().bold()); }
+        if render.colored() { return render.paint(arrow, Style::new().dimmed()); }
+        return String::from(if render.ascii { "." } else { "·" });
+    }).collect();
 
-            table.add_row(row);
+    return arrows.join(" ");
+}
+
+// the board & score lines, without clearing the screen or the controls
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let render: &RenderOptions = &self.render;
+        let text: &Strings = render.text();
+        let table: Table = self.table();
+        let rendered: String = table.to_string();
+        writeln!(f, "{}", rendered)?;
+        if self.paused == true {
+            writeln!(f, "{}", render.paint(text.paused_banner, Style::new().black().on_yellow().bold()))?;
+        }
+        if self.sandbox == true {
+            writeln!(f, "{} {}", render.paint(text.sandbox_banner, Style::new().black().on_cyan().bold()), text.sandbox_help)?;
+        }
+        writeln!(f, "{}{}", render.paint(text.legal_moves, Style::new().underline()), move_arrows(self))?;
+        // nothing left to spawn into, one wrong move away from the end
+        if self.is_board_full() == true && self.game_over() == false {
+            writeln!(f, "{}", render.paint(text.board_full, Style::new().red().bold()))?;
         }
 
-        // print everything
-        println!("{}c", 27 as char); // clear (terminal) screen
-        println!("{} or {} - Up/Left/Down/Right", "WASD".yellow().bold(), "Arrow Keys".yellow().bold());
-        println!("{} - Reset/New Game", "R".cyan().bold());
-        println!("{}/{} - Quit", "Q".red().bold(), "Esc".red().bold());
-        println!("{}", table);
-        println!("{}{}", "Score: ".underline(), self.score.green().bold().underline());
+        // `width` is the terminal width, the top border is plain text so its width is the table width
+        let table_width: usize = rendered.lines().next().map_or(0, |border| border.width());
+        if table.width().is_some_and(|terminal_width| table_width > terminal_width as usize) {
+            writeln!(f, "{}", render.paint(text.too_wide, Style::new().yellow()))?;
+        }
+        let best: String = match self.best {
+            Some(best) if self.config.mode.shows_best() => format!("  {}{}", render.paint(text.best, Style::new().underline()), render.paint(best.max(self.score), Style::new().yellow().bold().underline())),
+            _ => String::new()
+        };
+        let score: String = if self.score_capped() { format!("{} {}", self.score, text.score_max) } else { self.score.to_string() };
+        writeln!(f, "{}{}{}  {}{}  {}{}",
+            render.paint(text.score, Style::new().underline()), render.paint(score, Style::new().green().bold().underline()), best,
+            render.paint(text.moves, Style::new().underline()), render.paint(self.moves, Style::new().cyan().bold().underline()),
+            render.paint(text.target, Style::new().underline()), render.paint(self.config.target, Style::new().yellow().bold().underline())
+        )?;
+        writeln!(f, "{}{}", render.paint(text.quality, Style::new().underline()), render.paint(format!("{:.1}", self.quality()), Style::new().blue().bold().underline()))?;
+        if self.config.combo_scoring == true {
+            writeln!(f, "{}{}", render.paint(text.streak, Style::new().underline()), render.paint(self.streak, Style::new().magenta().bold().underline()))?;
+        }
+        if let Some(left) = self.time_left() {
+            let seconds: u64 = left.as_secs_f64().ceil() as u64;
+            writeln!(f, "{}{}", render.paint(text.time_left, Style::new().underline()), render.paint(format!("{}:{:02}", seconds / 60, seconds % 60), Style::new().red().bold().underline()))?;
+        }
+        if let Some(left) = self.move_time_left() {
+            writeln!(f, "{}{}", render.paint(text.move_time, Style::new().underline()), render.paint(format!("{}s", left.as_secs_f64().ceil() as u64), Style::new().red().bold().underline()))?;
+        }
+        if let Some(remaining) = self.merges_remaining {
+            writeln!(f, "{}{}", render.paint(text.merges_left, Style::new().underline()), render.paint(remaining, Style::new().yellow().bold().underline()))?;
+        }
 
         return Ok(());
     }
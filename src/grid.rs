@@ -0,0 +1,88 @@
+use std::ops::{Index, IndexMut};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::game::{Position, Tile};
+
+// tiles stored row by row, always indexed as (row, column), 0 is an empty cell
+// serialized as the plain list of rows
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct Grid {
+    cells: Vec<Vec<Tile>>
+}
+
+impl Grid {
+    // empty grid of the given size
+    pub fn new(width: usize, height: usize) -> Self {
+        return Self { cells: vec![vec![0; width]; height] };
+    }
+
+    pub fn width(&self) -> usize {
+        return self.cells.first().map_or(0, |row| row.len());
+    }
+
+    pub fn height(&self) -> usize {
+        return self.cells.len();
+    }
+
+    // `None` when the coordinates are outside of the grid
+    pub fn get(&self, row: usize, column: usize) -> Option<Tile> {
+        return self.cells.get(row)?.get(column).copied();
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, value: Tile) {
+        self.cells[row][column] = value;
+    }
+
+    pub fn rows(&self) -> &[Vec<Tile>] {
+        return &self.cells;
+    }
+
+    // copies of the columns, top to bottom
+    pub fn cols(&self) -> Vec<Vec<Tile>> {
+        return (0..self.width()).map(|column| self.column(column)).collect();
+    }
+
+    pub fn row(&self, row: usize) -> Vec<Tile> {
+        return self.cells[row].clone();
+    }
+
+    pub fn column(&self, column: usize) -> Vec<Tile> {
+        return self.cells.iter().map(|row| row[column]).collect();
+    }
+
+    pub fn set_row(&mut self, row: usize, values: Vec<Tile>) {
+        self.cells[row] = values;
+    }
+
+    pub fn set_column(&mut self, column: usize, values: &[Tile]) {
+        for (row, &value) in values.iter().enumerate() {
+            self.cells[row][column] = value;
+        }
+    }
+
+    // every tile in row-major order
+    pub fn tiles(&self) -> impl Iterator<Item = Tile> + '_ {
+        return self.cells.iter().flatten().copied();
+    }
+}
+
+impl From<Vec<Vec<Tile>>> for Grid {
+    fn from(cells: Vec<Vec<Tile>>) -> Self {
+        return Self { cells };
+    }
+}
+
+impl Index<Position> for Grid {
+    type Output = Tile;
+    fn index(&self, (row, column): Position) -> &Tile {
+        return &self.cells[row][column];
+    }
+}
+
+impl IndexMut<Position> for Grid {
+    fn index_mut(&mut self, (row, column): Position) -> &mut Tile {
+        return &mut self.cells[row][column];
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+// best score of every board configuration
+const HIGH_SCORE_FILE: &str = "highscore.txt";
+// fewest moves it took to reach the target, per board configuration
+const FEWEST_MOVES_FILE: &str = "fewest_moves.txt";
+
+// the platform config directory, shared by every program
+pub(crate) fn config_dir() -> Option<PathBuf> {
+    return env::var_os("XDG_CONFIG_HOME").map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+}
+
+// `file` in the game's own folder of the config directory
+pub(crate) fn path(file: &str) -> Option<PathBuf> {
+    return Some(config_dir()?.join("game_2048").join(file));
+}
+
+// one `KEY VALUE` line per board configuration, unreadable lines are skipped
+fn read_all(file: &str) -> HashMap<String, u64> {
+    let mut scores: HashMap<String, u64> = HashMap::new();
+    let Some(path) = path(file) else { return scores; };
+    let Ok(contents) = fs::read_to_string(path) else { return scores; };
+
+    for line in contents.lines() {
+        let mut parts = line.split_whitespace();
+        if let (Some(key), Some(Ok(score))) = (parts.next(), parts.next().map(str::parse::<u64>)) {
+            scores.insert(key.to_string(), score);
+        }
+    }
+
+    return scores;
+}
+
+fn write_all(file: &str, scores: &HashMap<String, u64>) -> io::Result<()> {
+    let path: PathBuf = path(file).ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No config directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut keys: Vec<&String> = scores.keys().collect();
+    keys.sort();
+    let contents: String = keys.iter().map(|key| format!("{} {}\n", key, scores[*key])).collect();
+    return fs::write(path, contents);
+}
+
+pub(crate) fn load(key: &str) -> u64 {
+    return read_all(HIGH_SCORE_FILE).get(key).copied().unwrap_or(0);
+}
+
+// stores `score` only when it beats the stored one
+pub(crate) fn save(key: &str, score: u64) -> io::Result<()> {
+    let mut scores: HashMap<String, u64> = read_all(HIGH_SCORE_FILE);
+    if scores.get(key).is_some_and(|&best| best >= score) { return Ok(()); }
+    scores.insert(key.to_string(), score);
+    return write_all(HIGH_SCORE_FILE, &scores);
+}
+
+// `None` until the target has been reached once
+pub(crate) fn load_fewest_moves(key: &str) -> Option<u32> {
+    return read_all(FEWEST_MOVES_FILE).get(key).map(|&moves| moves as u32);
+}
+
+// stores `moves` only when it's fewer than the stored ones
+pub(crate) fn save_fewest_moves(key: &str, moves: u32) -> io::Result<()> {
+    let mut records: HashMap<String, u64> = read_all(FEWEST_MOVES_FILE);
+    if records.get(key).is_some_and(|&fewest| fewest <= moves as u64) { return Ok(()); }
+    records.insert(key.to_string(), moves as u64);
+    return write_all(FEWEST_MOVES_FILE, &records);
+}
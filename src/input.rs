@@ -0,0 +1,55 @@
+use std::io;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+use getch_rs::{Getch, Key};
+
+#[derive(Debug)]
+pub enum Event {
+    Key(Key),
+    Tick // no key arrived within the tick, time to redraw
+}
+
+// where keys come from, either waiting for the next key or waking up every tick without one
+pub enum Input {
+    Blocking(Getch),
+    Ticking {
+        keys: Receiver<Key>,
+        tick: Duration,
+        _terminal: Getch // restores the terminal on drop, the reader thread's `Getch` never gets dropped
+    }
+}
+
+impl Input {
+    // waits for a key forever, same as reading `Getch` directly
+    pub fn blocking() -> Self {
+        return Input::Blocking(Getch::new());
+    }
+
+    // keys are read on their own thread, `next` returns `Event::Tick` when none arrives within `tick`
+    pub fn ticking(tick: Duration) -> Self {
+        // created before the thread's `Getch`, so it's the one that remembers the original terminal mode
+        let terminal: Getch = Getch::new();
+
+        let (sender, keys) = mpsc::channel::<Key>();
+        thread::spawn(move || {
+            let getch: Getch = Getch::new();
+            while let Ok(key) = getch.getch() {
+                if sender.send(key).is_err() { break; }
+            }
+        });
+
+        return Input::Ticking { keys, tick, _terminal: terminal };
+    }
+
+    pub fn next(&self) -> io::Result<Event> {
+        match self {
+            Input::Blocking(getch) => return Ok(Event::Key(getch.getch()?)),
+            Input::Ticking { keys, tick, .. } => match keys.recv_timeout(*tick) {
+                Ok(key) => return Ok(Event::Key(key)),
+                Err(RecvTimeoutError::Timeout) => return Ok(Event::Tick),
+                Err(RecvTimeoutError::Disconnected) => return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "keyboard reader stopped"))
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use getch_rs::Key;
+
+use crate::game::Keypress;
+
+// which key triggers which action, several keys can share one action
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyBindings {
+    bindings: HashMap<Key, Keypress>
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let mut bindings: KeyBindings = KeyBindings { bindings: HashMap::new() };
+
+        bindings.bind(Keypress::Up, &[Key::Char('w'), Key::Char('W'), Key::Up]);
+        bindings.bind(Keypress::Down, &[Key::Char('s'), Key::Char('S'), Key::Down]);
+        bindings.bind(Keypress::Left, &[Key::Char('a'), Key::Char('A'), Key::Left]);
+        bindings.bind(Keypress::Right, &[Key::Char('d'), Key::Char('D'), Key::Right]);
+        bindings.bind(Keypress::Reset, &[Key::Char('r'), Key::Char('R')]);
+        bindings.bind(Keypress::Save, &[Key::Char('p'), Key::Char('P')]);
+        bindings.bind(Keypress::Undo, &[Key::Char('u'), Key::Char('U')]);
+        bindings.bind(Keypress::Hint, &[Key::Char('h'), Key::Char('H')]);
+        bindings.bind(Keypress::Solve, &[Key::Char('v'), Key::Char('V')]);
+        bindings.bind(Keypress::Quit, &[Key::Char('q'), Key::Char('Q'), Key::Esc]);
+        bindings.bind(Keypress::Wider, &[Key::Char(']')]);
+        bindings.bind(Keypress::Narrower, &[Key::Char('[')]);
+        bindings.bind(Keypress::Taller, &[Key::Char('=')]);
+        bindings.bind(Keypress::Shorter, &[Key::Char('-')]);
+        bindings.bind(Keypress::Pause, &[Key::Char(' ')]);
+        bindings.bind(Keypress::Sandbox, &[Key::Char('b'), Key::Char('B')]);
+        bindings.bind(Keypress::Skip, &[Key::Char('n'), Key::Char('N')]);
+        for exponent in 0..=9 {
+            bindings.bind(Keypress::Place(exponent), &[Key::Char((b'0' + exponent) as char)]);
+        }
+
+        return bindings;
+    }
+}
+
+fn parse_action(name: &str) -> Option<Keypress> {
+    match name {
+        "up" => Some(Keypress::Up),
+        "down" => Some(Keypress::Down),
+        "left" => Some(Keypress::Left),
+        "right" => Some(Keypress::Right),
+        "reset" => Some(Keypress::Reset),
+        "save" => Some(Keypress::Save),
+        "undo" => Some(Keypress::Undo),
+        "hint" => Some(Keypress::Hint),
+        "solve" => Some(Keypress::Solve),
+        "quit" => Some(Keypress::Quit),
+        "wider" => Some(Keypress::Wider),
+        "narrower" => Some(Keypress::Narrower),
+        "taller" => Some(Keypress::Taller),
+        "shorter" => Some(Keypress::Shorter),
+        "pause" => Some(Keypress::Pause),
+        "sandbox" => Some(Keypress::Sandbox),
+        "skip" => Some(Keypress::Skip),
+        _ => None
+    }
+}
+
+// a single character is that character, longer names are the special keys
+fn parse_key(name: &str) -> Option<Key> {
+    let mut chars = name.chars();
+    if let (Some(character), None) = (chars.next(), chars.next()) {
+        return Some(Key::Char(character));
+    }
+
+    match name {
+        "up" => Some(Key::Up),
+        "down" => Some(Key::Down),
+        "left" => Some(Key::Left),
+        "right" => Some(Key::Right),
+        "esc" => Some(Key::Esc),
+        "space" => Some(Key::Char(' ')),
+        _ => None
+    }
+}
+
+impl KeyBindings {
+    pub fn resolve(&self, key: Key) -> Result<Keypress, &'static str> {
+        return self.bindings.get(&key).copied().ok_or("Invalid Key");
+    }
+
+    // every key that triggers `action`, in no particular order
+    pub fn keys(&self, action: Keypress) -> Vec<Key> {
+        return self.bindings.iter().filter(|&(_, &bound)| bound == action).map(|(key, _)| key.clone()).collect();
+    }
+
+    // `action` is triggered by `keys` only, a key bound elsewhere moves over to `action`
+    pub fn bind(&mut self, action: Keypress, keys: &[Key]) {
+        self.bindings.retain(|_, bound| *bound != action);
+        for key in keys {
+            self.bindings.insert(key.clone(), action);
+        }
+    }
+
+    // starts from the default bindings, every `ACTION = KEY KEY ...` line replaces the keys of that action
+    // e.g. `left = h left`, lines starting with `#` are comments
+    pub fn load(path: &Path) -> Result<KeyBindings, Box<dyn Error>> {
+        let contents: String = fs::read_to_string(path)?;
+        let mut bindings: KeyBindings = KeyBindings::default();
+
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') { continue; }
+
+            let (action, keys) = line.split_once('=').ok_or_else(|| format!("Invalid key binding line: {}", line))?;
+            bindings.rebind(action.trim(), keys)?;
+        }
+
+        return Ok(bindings);
+    }
+
+    // `bind` by name, e.g. `rebind("left", "h left")`, shared by the key file & the config file
+    pub fn rebind(&mut self, action: &str, keys: &str) -> Result<(), String> {
+        let action: Keypress = parse_action(action).ok_or_else(|| format!("Invalid key binding action: {}", action))?;
+        let keys: Vec<Key> = keys.split_whitespace()
+            .map(|key| parse_key(key).ok_or_else(|| format!("Invalid key: {}", key)))
+            .collect::<Result<Vec<Key>, String>>()?;
+
+        self.bind(action, &keys);
+        return Ok(());
+    }
+}
@@ -0,0 +1,345 @@
+use crate::game::{Keypress, Tile, DIRECTIONS};
+
+// language of the text shown while playing, the flags & their help stay english like the flag names
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Lang {
+    #[default]
+    English,
+    German
+}
+
+impl Lang {
+    pub fn from_name(name: &str) -> Option<Lang> {
+        match name {
+            "en" => Some(Lang::English),
+            "de" => Some(Lang::German),
+            _ => None
+        }
+    }
+
+    pub fn strings(self) -> &'static Strings {
+        match self {
+            Lang::English => return &ENGLISH,
+            Lang::German => return &GERMAN
+        }
+    }
+}
+
+// every piece of text the game shows, text with numbers in it is a function so each language can order the words itself
+pub struct Strings {
+    // board
+    pub legal_moves: &'static str,
+    pub paused_banner: &'static str,
+    pub sandbox_banner: &'static str,
+    pub sandbox_help: &'static str,
+    pub board_full: &'static str,
+    pub too_wide: &'static str,
+    pub score: &'static str,
+    pub score_max: &'static str,
+    pub best: &'static str,
+    pub moves: &'static str,
+    pub target: &'static str,
+    pub quality: &'static str,
+    pub streak: &'static str,
+    pub time_left: &'static str,
+    pub move_time: &'static str,
+    pub merges_left: &'static str,
+    pub directions: [&'static str; 4], // in `DIRECTIONS` order
+
+    // controls
+    pub space_key: &'static str,
+    pub control_move: &'static str,
+    pub control_reset: &'static str,
+    pub control_undo: &'static str,
+    pub control_hint: &'static str,
+    pub control_solve: &'static str,
+    pub control_save: &'static str,
+    pub control_pause: &'static str,
+    pub control_sandbox: &'static str, // followed by the number keys
+    pub control_place: &'static str,
+    pub control_width: &'static str,
+    pub control_height: &'static str,
+    pub control_quit: &'static str,
+
+    // messages under the board
+    pub nice_move: &'static str,
+    pub merged: fn(base: Tile, times: Tile, merged: Tile, others: u32, points: u64) -> String,
+    pub game_over: &'static str,
+    pub undo_or_quit: fn(undo: &str) -> String, // the undo key
+    pub time_up: &'static str,
+    pub you_win: &'static str,
+    pub keep_going: &'static str,
+    pub reached_in: fn(target: Tile, moves: u32) -> String,
+    pub fewest: fn(moves: u32) -> String,
+    pub new_record: &'static str,
+    pub reached: fn(target: Tile) -> String,
+    pub undone: &'static str,
+    pub resized: fn(width: usize, height: usize) -> String,
+    pub paused: &'static str,
+    pub resumed: &'static str,
+    pub sandbox_entered: &'static str,
+    pub sandbox_left: &'static str,
+    pub transposed: &'static str,
+    pub too_slow: &'static str,
+    pub hint: &'static str,
+    pub solution: fn(moves: usize, score: u64) -> String,
+    pub invalid_key: &'static str,
+    pub invalid_key_named: fn(key: char) -> String,
+    pub reset_cancelled: &'static str,
+    pub unnecessary_move: &'static str,
+    pub reset_prompt: &'static str,
+    pub saved: fn(path: &str) -> String,
+    pub daily: &'static str,
+    pub replay_move: &'static str,
+    pub replay_finished: &'static str,
+    pub ai_move: &'static str,
+    pub stopped: &'static str,
+    pub key: &'static str,
+    pub total_score: &'static str,
+    pub interrupted: fn(path: &str) -> String,
+    pub no_recovery: &'static str,
+    pub resume_prompt: &'static str,
+    pub save_high_score_failed: &'static str,
+    pub save_fewest_moves_failed: &'static str,
+    pub save_replay_failed: &'static str,
+    pub save_stats_failed: &'static str,
+    pub save_interrupted_failed: &'static str,
+
+    // summary after the game
+    pub summary: &'static str,
+    pub summary_score: &'static str,
+    pub summary_moves: &'static str,
+    pub largest_tile: &'static str,
+    pub time: &'static str,
+    pub share: &'static str,
+
+    // tutorial
+    pub tutorial: &'static str,
+    pub progress: &'static str,
+    pub skip_step: &'static str,
+    pub step_skipped: &'static str,
+    pub step_first_merge: &'static str,
+    pub step_reach_eight: &'static str,
+    pub step_keep_space: fn(moves: u32, empty: usize) -> String,
+    pub step_done: &'static str,
+    pub stuck: &'static str,
+    pub saving_disabled: &'static str,
+    pub tutorial_solution: fn(moves: usize) -> String,
+    pub tutorial_resized: &'static str,
+    pub tutorial_sandbox: &'static str,
+    pub tutorial_sandbox_left: &'static str
+}
+
+impl Strings {
+    // directions by name, anything else as the keypress itself
+    pub fn keypress(&self, keypress: Keypress) -> String {
+        match DIRECTIONS.iter().position(|&direction| direction == keypress) {
+            Some(index) => return self.directions[index].to_string(),
+            None => return format!("{:?}", keypress)
+        }
+    }
+}
+
+const ENGLISH: Strings = Strings {
+    legal_moves: "Legal moves: ",
+    paused_banner: "=== PAUSED ===",
+    sandbox_banner: "=== SANDBOX ===",
+    sandbox_help: "arrows move the cursor, 0-9 place tiles, B plays on",
+    board_full: "BOARD FULL",
+    too_wide: "The board is wider than the terminal, try a smaller grid or a wider window",
+    score: "Score: ",
+    score_max: "(max)",
+    best: "Best: ",
+    moves: "Moves: ",
+    target: "Target: ",
+    quality: "Quality: ",
+    streak: "Streak: ",
+    time_left: "Time left: ",
+    move_time: "Move time: ",
+    merges_left: "Merges left: ",
+    directions: ["Left", "Right", "Up", "Down"],
+
+    space_key: "Space",
+    control_move: "Up/Left/Down/Right",
+    control_reset: "Reset/New Game",
+    control_undo: "Undo",
+    control_hint: "Hint",
+    control_solve: "Show how the AI would play it out",
+    control_save: "Save Game",
+    control_pause: "Pause/Resume",
+    control_sandbox: "Sandbox, arrows move a cursor &",
+    control_place: "place tiles",
+    control_width: "Narrower/Wider",
+    control_height: "Shorter/Taller",
+    control_quit: "Quit",
+
+    nice_move: "--- Nice Move ---",
+    merged: |base, times, merged, others, points| {
+        let others: String = if others > 0 { format!(" and {} more", others) } else { String::new() };
+        return format!("Merged {}×{} → {}{}, +{} pts", base, times, merged, others, points);
+    },
+    game_over: "--- Game Over ---",
+    undo_or_quit: |undo| format!("{} takes the last move back, any other key ends the game", undo),
+    time_up: "--- Time's up ---",
+    you_win: "=== YOU WIN ===",
+    keep_going: "Keep going!",
+    reached_in: |target, moves| format!("Reached {} in {} moves.", target, moves),
+    fewest: |moves| format!("Fewest: {}.", moves),
+    new_record: "New record!",
+    reached: |target| format!("--- Reached {} ---", target),
+    undone: "--- Move undone ---",
+    resized: |width, height| format!("--- Board resized to {}x{} ---", width, height),
+    paused: "--- Paused, space resumes ---",
+    resumed: "--- Resumed ---",
+    sandbox_entered: "--- Sandbox, arrows move the cursor, 0-9 place tiles ---",
+    sandbox_left: "--- Playing on from the edited board ---",
+    transposed: "--- Board transposed ---",
+    too_slow: "--- Too slow, a penalty tile landed ---",
+    hint: "Try:",
+    solution: |moves, score| format!("AI plays {} moves to a score of {}:", moves, score),
+    invalid_key: "--- Invalid key ---",
+    invalid_key_named: |key| format!("--- Invalid key {:?} ---", key),
+    reset_cancelled: "--- Reset cancelled ---",
+    unnecessary_move: "--- Unnecessary move ---",
+    reset_prompt: "Reset? (y/n)",
+    saved: |path| format!("--- Game saved to {} ---", path),
+    daily: "Daily challenge",
+    replay_move: "Replay move",
+    replay_finished: "--- Replay finished ---",
+    ai_move: "AI move",
+    stopped: "--- Stopped ---",
+    key: "Key",
+    total_score: "Total score",
+    interrupted: |path| format!("--- Interrupted, game saved to {}, continue with --resume ---", path),
+    no_recovery: "No interrupted game to resume, starting a new one",
+    resume_prompt: "Resume the interrupted game? (y/n)",
+    save_high_score_failed: "Couldn't save the high score:",
+    save_fewest_moves_failed: "Couldn't save the fewest moves:",
+    save_replay_failed: "Couldn't save the replay:",
+    save_stats_failed: "Couldn't save the stats:",
+    save_interrupted_failed: "Couldn't save the interrupted game:",
+
+    summary: "Summary",
+    summary_score: "Score",
+    summary_moves: "Moves",
+    largest_tile: "Largest tile",
+    time: "Time",
+    share: "Share",
+
+    tutorial: "Tutorial:",
+    progress: "Progress: ",
+    skip_step: "Skip step",
+    step_skipped: "--- Step skipped ---",
+    step_first_merge: "Slide two equal tiles into each other to merge them.",
+    step_reach_eight: "Merges keep doubling the tile. Build an 8 tile.",
+    step_keep_space: |moves, empty| format!("Space is life. Make {} moves in a row keeping at least {} empty cells.", moves, empty),
+    step_done: "Tutorial complete, you're ready to play!",
+    stuck: "--- Stuck, here's a fresh board ---",
+    saving_disabled: "--- Saving is disabled in the tutorial ---",
+    tutorial_solution: |moves| format!("The AI would play {} more moves from here", moves),
+    tutorial_resized: "--- Board resized ---",
+    tutorial_sandbox: "--- Sandbox, B plays on ---",
+    tutorial_sandbox_left: "--- Back to playing ---"
+};
+
+const GERMAN: Strings = Strings {
+    legal_moves: "Mögliche Züge: ",
+    paused_banner: "=== PAUSIERT ===",
+    sandbox_banner: "=== SANDKASTEN ===",
+    sandbox_help: "Pfeiltasten bewegen den Cursor, 0-9 setzen Kacheln, B spielt weiter",
+    board_full: "BRETT VOLL",
+    too_wide: "Das Brett ist breiter als das Terminal, versuche ein kleineres Brett oder ein breiteres Fenster",
+    score: "Punkte: ",
+    score_max: "(max)",
+    best: "Rekord: ",
+    moves: "Züge: ",
+    target: "Ziel: ",
+    quality: "Qualität: ",
+    streak: "Serie: ",
+    time_left: "Restzeit: ",
+    move_time: "Zugzeit: ",
+    merges_left: "Verschmelzungen übrig: ",
+    directions: ["Links", "Rechts", "Hoch", "Runter"],
+
+    space_key: "Leertaste",
+    control_move: "Hoch/Links/Runter/Rechts",
+    control_reset: "Zurücksetzen/Neues Spiel",
+    control_undo: "Rückgängig",
+    control_hint: "Tipp",
+    control_solve: "Zeigen, wie die KI zu Ende spielen würde",
+    control_save: "Spiel speichern",
+    control_pause: "Pause/Weiter",
+    control_sandbox: "Sandkasten, Pfeiltasten bewegen einen Cursor &",
+    control_place: "setzen Kacheln",
+    control_width: "Schmaler/Breiter",
+    control_height: "Niedriger/Höher",
+    control_quit: "Beenden",
+
+    nice_move: "--- Guter Zug ---",
+    merged: |base, times, merged, others, points| {
+        let others: String = if others > 0 { format!(" und {} weitere", others) } else { String::new() };
+        return format!("Verschmolzen {}×{} → {}{}, +{} Punkte", base, times, merged, others, points);
+    },
+    game_over: "--- Spiel vorbei ---",
+    undo_or_quit: |undo| format!("{} nimmt den letzten Zug zurück, jede andere Taste beendet das Spiel", undo),
+    time_up: "--- Zeit abgelaufen ---",
+    you_win: "=== GEWONNEN ===",
+    keep_going: "Weiter so!",
+    reached_in: |target, moves| format!("{} in {} Zügen erreicht.", target, moves),
+    fewest: |moves| format!("Bestwert: {}.", moves),
+    new_record: "Neuer Rekord!",
+    reached: |target| format!("--- {} erreicht ---", target),
+    undone: "--- Zug rückgängig gemacht ---",
+    resized: |width, height| format!("--- Brett auf {}x{} geändert ---", width, height),
+    paused: "--- Pausiert, Leertaste setzt fort ---",
+    resumed: "--- Fortgesetzt ---",
+    sandbox_entered: "--- Sandkasten, Pfeiltasten bewegen den Cursor, 0-9 setzen Kacheln ---",
+    sandbox_left: "--- Weiter mit dem bearbeiteten Brett ---",
+    transposed: "--- Brett transponiert ---",
+    too_slow: "--- Zu langsam, eine Strafkachel ist gelandet ---",
+    hint: "Versuche:",
+    solution: |moves, score| format!("Die KI spielt {} Züge bis {} Punkte:", moves, score),
+    invalid_key: "--- Ungültige Taste ---",
+    invalid_key_named: |key| format!("--- Ungültige Taste {:?} ---", key),
+    reset_cancelled: "--- Zurücksetzen abgebrochen ---",
+    unnecessary_move: "--- Unnötiger Zug ---",
+    reset_prompt: "Zurücksetzen? (y/n)",
+    saved: |path| format!("--- Spiel in {} gespeichert ---", path),
+    daily: "Tägliche Herausforderung",
+    replay_move: "Wiederholter Zug",
+    replay_finished: "--- Wiederholung beendet ---",
+    ai_move: "KI-Zug",
+    stopped: "--- Angehalten ---",
+    key: "Taste",
+    total_score: "Gesamtpunkte",
+    interrupted: |path| format!("--- Unterbrochen, Spiel in {} gespeichert, weiter mit --resume ---", path),
+    no_recovery: "Kein unterbrochenes Spiel zum Fortsetzen, ein neues beginnt",
+    resume_prompt: "Unterbrochenes Spiel fortsetzen? (y/n)",
+    save_high_score_failed: "Rekord konnte nicht gespeichert werden:",
+    save_fewest_moves_failed: "Wenigste Züge konnten nicht gespeichert werden:",
+    save_replay_failed: "Aufzeichnung konnte nicht gespeichert werden:",
+    save_stats_failed: "Statistik konnte nicht gespeichert werden:",
+    save_interrupted_failed: "Unterbrochenes Spiel konnte nicht gespeichert werden:",
+
+    summary: "Zusammenfassung",
+    summary_score: "Punkte",
+    summary_moves: "Züge",
+    largest_tile: "Größte Kachel",
+    time: "Zeit",
+    share: "Teilen",
+
+    tutorial: "Anleitung:",
+    progress: "Fortschritt: ",
+    skip_step: "Schritt überspringen",
+    step_skipped: "--- Schritt übersprungen ---",
+    step_first_merge: "Schiebe zwei gleiche Kacheln ineinander, um sie zu verschmelzen.",
+    step_reach_eight: "Verschmelzen verdoppelt die Kachel immer weiter. Baue eine 8.",
+    step_keep_space: |moves, empty| format!("Platz ist Leben. Mache {} Züge in Folge mit mindestens {} freien Feldern.", moves, empty),
+    step_done: "Anleitung abgeschlossen, du bist bereit zum Spielen!",
+    stuck: "--- Festgefahren, hier ist ein neues Brett ---",
+    saving_disabled: "--- Speichern ist in der Anleitung deaktiviert ---",
+    tutorial_solution: |moves| format!("Die KI würde von hier noch {} Züge spielen", moves),
+    tutorial_resized: "--- Brett geändert ---",
+    tutorial_sandbox: "--- Sandkasten, B spielt weiter ---",
+    tutorial_sandbox_left: "--- Zurück zum Spiel ---"
+};
@@ -0,0 +1,14 @@
+pub mod game;
+pub mod grid;
+pub mod error;
+pub mod auto;
+pub mod bitboard;
+pub mod replay;
+pub mod render;
+pub mod keybindings;
+pub mod input;
+pub mod multi;
+pub mod lang;
+#[cfg(feature = "serde")]
+pub mod settings;
+mod highscore;
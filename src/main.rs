@@ -1,89 +1,832 @@
 use std::error::Error;
-use getch_rs::Getch;
-use owo_colors::OwoColorize;
-mod game;
-use game::{Game, BoardConfig, GameResult};
-
-// parse arguments into board configuration
-// if anything `bad` happens just use default configuration
-fn parse_args(args: &Vec<String>) -> Option<BoardConfig> {
+use std::fmt::Display;
+use std::io::{BufRead, IsTerminal};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use owo_colors::Style;
+use getch_rs::Key;
+use game_2048::game::{Game, BoardConfig, GameResult, Keypress, Mode, MoveSummary, MoveTimer, SpawnMode, Tile, TimeoutPenalty, WALL};
+use game_2048::auto;
+use game_2048::render::{RenderOptions, Theme, TileGlyphs};
+use game_2048::keybindings::KeyBindings;
+use game_2048::settings::Settings;
+use game_2048::input::{Input, Event};
+use game_2048::multi::MultiGame;
+use game_2048::replay::Replay;
+use game_2048::lang::{Lang, Strings};
+
+mod tutorial;
+
+// delay between two AI moves, so the game can be followed, a keypress can stop the AI in between
+const AI_MOVE_DELAY: Duration = Duration::from_millis(100);
+// delay between two replayed moves
+const REPLAY_MOVE_DELAY: Duration = Duration::from_millis(200);
+// how often a timed game redraws its clock
+const CLOCK_TICK: Duration = Duration::from_secs(1);
+// how often the move timer is checked, a move can run this much over its time
+const MOVE_TIMER_TICK: Duration = Duration::from_millis(100);
+// `--move-timer` defaults, each move gets 98% of the last one's time but never less than a second
+const MOVE_TIMER_DECAY: f64 = 0.98;
+const MOVE_TIMER_FLOOR: Duration = Duration::from_secs(1);
+// in-between frames & their delay for `--animate`, unless given explicitly
+const ANIMATION_FRAMES: usize = 2;
+const ANIMATION_FRAME_DELAY: Duration = Duration::from_millis(40);
+// how long merged tiles stay highlighted with `--flash`
+const MERGE_FLASH_DURATION: Duration = Duration::from_millis(150);
+// where the save keypress writes the game to
+const SAVE_PATH: &str = "save.json";
+// where an interrupted game is saved to, `--resume` offers to continue it
+const RECOVERY_PATH: &str = "recovery.json";
+
+// removes `--name VALUE` from the arguments and returns the value
+fn take_flag(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let index: usize = args.iter().position(|arg| arg == name)?;
+    args.remove(index);
+    if index < args.len() {
+        return Some(args.remove(index));
+    }
+
+    return None;
+}
+
+// `take_flag` & parse the value, a value that doesn't parse is an error instead of being ignored
+fn take_parsed<T: FromStr>(args: &mut Vec<String>, name: &str) -> Result<Option<T>, String> where T::Err: Display {
+    let Some(value) = take_flag(args, name) else { return Ok(None); };
+    return value.parse().map(Some).map_err(|error| format!("invalid value for {}: {}: {}", name, value, error));
+}
+
+// `--name SECONDS`, fractions like `0.5` too
+fn take_seconds(args: &mut Vec<String>, name: &str) -> Result<Option<Duration>, String> {
+    let Some(seconds) = take_parsed::<f64>(args, name)? else { return Ok(None); };
+    return Duration::try_from_secs_f64(seconds).map(Some).map_err(|error| format!("invalid value for {}: {}: {}", name, seconds, error));
+}
+
+// removes `--name` from the arguments and returns whether it was there
+fn take_switch(args: &mut Vec<String>, name: &str) -> bool {
+    let Some(index) = args.iter().position(|arg| arg == name) else { return false; };
+    args.remove(index);
+    return true;
+}
+
+// parse arguments into board configuration, the rest of `base` stays as it is
+// the error says which argument is bad and why
+fn parse_args(args: &[String], base: BoardConfig) -> Result<BoardConfig, String> {
     if args.len() != 3 {
-        println!("Not enought arguments. Using default configuration.");
-        return None;
+        return Err(format!("Expected 3 numbers (width height count), got {}", args.len()));
+    }
+
+    let names: [&str; 3] = ["width", "height", "count"];
+    let mut numbers: Vec<usize> = vec![];
+    for (name, arg) in names.iter().zip(args) {
+        let number: usize = arg.parse().map_err(|_| format!("{} must be a whole number, got '{}'", name, arg))?;
+        if number == 0 {
+            return Err(format!("{} must be > 0", name));
+        }
+        numbers.push(number);
+    }
+
+    let (width, height, count) = (numbers[0], numbers[1], numbers[2]);
+    let cells: usize = width * height;
+    if count >= cells {
+        return Err(format!("count {} needs at least one free cell, the board only has {} cells", count, cells));
     }
 
-    let numbers: Vec<usize> = args.iter().filter_map(|s| s.parse().ok()).collect();
-    if numbers.len() != 3 {
-        println!("Invalid arguments. Using default configuration.");
-        return None;
+    return Ok(BoardConfig { width, height, count, ..base });
+}
+
+// comma separated `VALUE:WEIGHT` pairs, like `2:9,4:1`
+fn parse_spawn_values(text: &str) -> Result<Vec<(Tile, f64)>, String> {
+    return text.split(',').map(|pair| {
+        let (value, weight) = pair.split_once(':').ok_or_else(|| format!("Expected VALUE:WEIGHT, got '{}'", pair))?;
+        let value: Tile = value.trim().parse().map_err(|_| format!("Spawn value must be a whole number, got '{}'", value))?;
+        let weight: f64 = weight.trim().parse().map_err(|_| format!("Spawn weight must be a number, got '{}'", weight))?;
+        return Ok((value, weight));
+    }).collect();
+}
+
+// grid of tiles, one row per line, separated by whitespace or commas, 0 for empty cells & # for walls
+fn read_board(path: &str) -> Result<Vec<Vec<Tile>>, Box<dyn Error>> {
+    let contents: String = std::fs::read_to_string(path)?;
+    let mut board: Vec<Vec<Tile>> = vec![];
+
+    for line in contents.lines().filter(|line| line.trim().is_empty() == false) {
+        let row: Vec<Tile> = line.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|value| value.is_empty() == false)
+            .map(|value| if value == "#" { Ok(WALL) } else { value.parse::<Tile>() })
+            .collect::<Result<Vec<Tile>, _>>()?;
+        board.push(row);
     }
 
-    return Some(BoardConfig { width: numbers[0], height: numbers[1], count: numbers[2] });
+    return Ok(board);
+}
+
+// (year, month, day) of a day counted from 1970-01-01, proleptic gregorian calendar
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z: u64 = days + 719468;
+    let era: u64 = z / 146097;
+    let day_of_era: u64 = z % 146097;
+    let year_of_era: u64 = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year: u64 = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index: u64 = (5 * day_of_year + 2) / 153; // march is 0
+    let day: u64 = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month: u64 = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year: u64 = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    return (year, month, day);
+}
+
+// today's UTC date as `YYYYMMDD`, the same seed for everyone on the same day
+fn daily_seed() -> u64 {
+    let days: u64 = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs() / 86400);
+    let (year, month, day) = civil_from_days(days);
+    return year * 10000 + month * 100 + day;
 }
 
-fn print_usage() {
-    println!("{}: game_2048 [CONFIG] [FLAGS]", "Usage".green());
+fn print_usage(color: bool) {
+    let render: RenderOptions = RenderOptions { color, ..Default::default() };
+    let paint = |text: &str, style: Style| render.paint(text, style);
+    let flag: Style = Style::new().bright_blue();
+
+    println!("{}: game_2048 [CONFIG] [FLAGS]", paint("Usage", Style::new().green()));
     println!();
-    println!("{} - {} {} {}", "Config".green(), "NUMBER".bright_red(), "NUMBER".bright_yellow(), "NUMBER".bright_magenta());
+    println!("{} - {} {} {}", paint("Config", Style::new().green()), paint("NUMBER", Style::new().bright_red()), paint("NUMBER", Style::new().bright_yellow()), paint("NUMBER", Style::new().bright_magenta()));
     println!(" - consists of three numbers");
-    println!(" - {} - Width of the grid", "Grid width".bright_red());
-    println!(" - {} - Height of the grid", "Grid height".bright_yellow());
-    println!(" - {} - Number of filled in tiles", "Filled count".bright_magenta());
-    println!(" - {}: {}", "default value".underline(), "4 4 2".bold());
+    println!(" - {} - Width of the grid", paint("Grid width", Style::new().bright_red()));
+    println!(" - {} - Height of the grid", paint("Grid height", Style::new().bright_yellow()));
+    println!(" - {} - Number of filled in tiles", paint("Filled count", Style::new().bright_magenta()));
+    println!(" - {}: {}", paint("default value", Style::new().underline()), paint("4 4 2", Style::new().bold()));
     println!();
     println!("Flags:");
-    println!(" {}, {} - Displays the help message", "-h".bright_blue(), "--help".bright_blue());
+    println!(" {}, {} - Displays the help message", paint("-h", flag), paint("--help", flag));
+    println!(" {} {} - Seeds the random generator, same seed & moves give the same game", paint("--seed", flag), paint("NUMBER", flag));
+    println!(" {} - Reset deals the same starting board again instead of a new one", paint("--sticky-seed", flag));
+    println!(" {} - Reset asks first and only goes through on {}", paint("--confirm-reset", flag), paint("y", flag));
+    println!(" {} - Daily challenge, everyone gets the same game today, seeded with the UTC date", paint("--daily", flag));
+    println!(" {} {} - Records the game's keypresses to a replay file, only new games without a move timer", paint("--record", flag), paint("PATH", flag));
+    println!(" {} {} - Plays back a recorded replay file", paint("--replay", flag), paint("PATH", flag));
+    println!(" {} {} - Continues a saved game", paint("--load", flag), paint("PATH", flag));
+    println!(" {} {} - Starts from the board in the file, one row per line, 0 for empty cells, # for walls", paint("--board", flag), paint("PATH", flag));
+    println!(" {} {} - Starts from a board shared as a code, the summary prints one for the last board", paint("--import", flag), paint("CODE", flag));
+    println!(" {} {} - Plays {} boards side by side, every keypress moves all of them", paint("--boards", flag), paint("N", flag), paint("N", flag));
+    println!(" {} - Practice mode, no new tiles appear after a move", paint("--no-spawn", flag));
+    println!(" {} - Puzzle mode, tiles always fall down, left & right rotate the columns, up does nothing", paint("--gravity", flag));
+    println!(" {} - Tiles wrap around the edges, the first & last tile of a row or column can merge", paint("--wrap", flag));
+    println!(" {} - Tiles of {} get more likely as the largest tile grows, {} more per doubling up to {}", paint("--adaptive-spawn", flag), paint("4", flag), paint("1%", flag), paint("30%", flag));
+    println!(" {} - Merging moves in a row score more, {} more per move in the streak", paint("--combo", flag), paint("10%", flag));
+    println!(" {} {} - Spawns {} tiles after every move instead of one", paint("--spawns", flag), paint("N", flag), paint("N", flag));
+    println!(" {} {} - New & starting tiles picked by weight, like {}", paint("--spawn-values", flag), paint("VALUE:WEIGHT,...", flag), paint("2:9,4:1", flag));
+    println!(" {} {} - Places {} immovable walls on the board", paint("--blockers", flag), paint("N", flag), paint("N", flag));
+    println!(" {} {} - Loads key bindings, one {} line each", paint("--keys", flag), paint("PATH", flag), paint("ACTION = KEY KEY ...", flag));
+    println!(" {} {} - Defaults read from a toml file instead of {} in the config directory", paint("--config", flag), paint("PATH", flag), paint("rust_2048/config.toml", flag));
+    println!(" {} {} - Tiles show custom text like emoji, one {} line each", paint("--glyphs", flag), paint("PATH", flag), paint("VALUE = TEXT", flag));
+    println!(" {} {} - Writes the score after every move to a csv file on exit", paint("--stats-out", flag), paint("PATH", flag));
+    println!(" {} {} - {} has no undo, hides the high score & spawns more 4s", paint("--mode", flag), paint("hardcore", flag), paint("hardcore", flag));
+    println!(" {} - {} transposes square boards, to check that moves behave the same when mirrored", paint("--debug", flag), paint("T", flag));
+    println!(" {} - Offers to continue the game Ctrl-C saved to {}", paint("--resume", flag), paint(RECOVERY_PATH, flag));
+    println!(" {} {} - Tile that wins the game, {} by default", paint("--target", flag), paint("N", flag), paint("2048", flag));
+    println!(" {} - Reaching the target ends the game with a win instead of playing on", paint("--stop-at-target", flag));
+    println!(" {} {} - Timed game, score as much as possible in {} seconds", paint("--time-limit", flag), paint("SECONDS", flag), paint("SECONDS", flag));
+    println!(" {} {} - Frantic game, every move has to come within the time, a penalty tile spawns when it doesn't", paint("--move-timer", flag), paint("SECONDS", flag));
+    println!("   {} {} - Each move gets this fraction of the last one's time, {} by default", paint("--move-decay", flag), paint("FACTOR", flag), paint(&MOVE_TIMER_DECAY.to_string(), flag));
+    println!("   {} {} - The move time never drops below this, {} by default", paint("--move-floor", flag), paint("SECONDS", flag), paint(&MOVE_TIMER_FLOOR.as_secs_f64().to_string(), flag));
+    println!("   {} - Running out of time ends the game instead", paint("--timeout-ends", flag));
+    println!(" {} - One json line per keypress instead of the board, for scripts", paint("--json", flag));
+    println!(" {} - Tiles slide to their new place, {} & {} {} tune it", paint("--animate", flag), paint("--animate-frames N", flag), paint("--frame-delay", flag), paint("MILLISECONDS", flag));
+    println!(" {} - Merged tiles light up for a moment after each move", paint("--flash", flag));
+    println!(" {} - Plain ascii board without colors", paint("--ascii", flag));
+    println!(" {} {} - Language of the game, {} or {}, this help stays english", paint("--lang", flag), paint("NAME", flag), paint("en", flag), paint("de", flag));
+    println!(" {} {} - Tile colors, {}, {} or {}", paint("--theme", flag), paint("NAME", flag), paint("classic", flag), paint("ocean", flag), paint("mono", flag));
+    println!(" {} {} - Draws {} dimmed in empty cells, e.g. {} or {}", paint("--empty-glyph", flag), paint("CHAR", flag), paint("CHAR", flag), paint("·", flag), paint("-", flag));
+    println!(" {} - Tiles show their exponent, {} instead of {}", paint("--exponent", flag), paint("11", flag), paint("2048", flag));
+    println!(" {} - No colors or other escape codes anywhere, same as setting {}", paint("--no-color", flag), paint("NO_COLOR", flag));
+    println!(" {} - Every board is printed below the last one, the screen is never cleared", paint("--no-clear", flag));
+    println!(" {} - Guided tutorial for new players", paint("--tutorial", flag));
+    println!(" {} {} - AI plays the game, looking {} moves ahead", paint("--ai-depth", flag), paint("N", flag), paint("N", flag));
+    println!(" {} - Plays the characters piped into stdin as keys, e.g. {}", paint("--stdin", flag), paint("echo wasd | game_2048 --stdin", flag));
+    println!(" {} - AI plays with the hint heuristic until it wins or gets stuck", paint("--autoplay", flag));
     println!();
 }
 
+// the biggest merge of the move & the points it scored, e.g. "Merged 2×64 → 128, +256 pts"
+fn merge_message(text: &Strings, summary: &MoveSummary, base: Tile) -> String {
+    if summary.merges == 0 { return String::from(text.nice_move); }
+
+    return (text.merged)(base, summary.largest_merge / base, summary.largest_merge, summary.merges - 1, summary.points);
+}
+
+// one letter per direction, e.g. "LLDRU", other keypresses are left out
+fn move_letters(moves: &[Keypress]) -> String {
+    return moves.iter().filter_map(|keypress| match keypress {
+        Keypress::Left => Some('L'),
+        Keypress::Right => Some('R'),
+        Keypress::Up => Some('U'),
+        Keypress::Down => Some('D'),
+        _ => None
+    }).collect();
+}
+
+// a failed write shouldn't end the game, just let the player know
+fn save_high_score(game: &Game) {
+    if let Err(error) = game.save_high_score() {
+        println!("{} {}", game.render.paint(game.render.text().save_high_score_failed, Style::new().red()), error);
+    }
+}
+
+// the moves it took to reach the target, and whether that's a new record
+fn reached_target(game: &Game) -> String {
+    let text: &Strings = game.render.text();
+    let message: String = (text.reached_in)(game.config.target, game.moves());
+    let record: Option<u32> = game.fewest_moves();
+    if let Err(error) = game.save_fewest_moves() {
+        println!("{} {}", game.render.paint(text.save_fewest_moves_failed, Style::new().red()), error);
+    }
+
+    match record {
+        Some(fewest) if fewest <= game.moves() => return format!("{} {}", message, (text.fewest)(fewest)),
+        _ => return format!("{} {}", message, text.new_record)
+    }
+}
+
+// whole seconds the clocks show, a tick only redraws the board when they change
+fn clock_seconds(game: &Game) -> (Option<u64>, Option<u64>) {
+    let seconds = |left: Duration| left.as_secs_f64().ceil() as u64;
+    return (game.time_left().map(seconds), game.move_time_left().map(seconds));
+}
+
+// message under the board, left out of json output so every line stays parseable
+fn status(game: &Game, message: impl std::fmt::Display) {
+    if game.render.json == false {
+        println!("{}", message);
+    }
+}
+
+fn print_summary(game: &Game) {
+    if game.render.json == true { return; }
+
+    let text: &Strings = game.render.text();
+    let elapsed: u64 = game.elapsed().as_secs();
+    println!();
+    println!("{}", game.render.paint(text.summary, Style::new().green().bold().underline()));
+    println!(" {}: {}", game.render.paint(text.summary_score, Style::new().green()), game.score());
+    println!(" {}: {}", game.render.paint(text.summary_moves, Style::new().green()), game.moves());
+    println!(" {}: {}", game.render.paint(text.largest_tile, Style::new().green()), game.largest_tile());
+    println!(" {}: {}:{:02}", game.render.paint(text.time, Style::new().green()), elapsed / 60, elapsed % 60);
+    println!(" {}: {}", game.render.paint(text.share, Style::new().green()), game.export_code());
+}
+
+// writes the recording of the current game, if it's being recorded
+fn save_recording(game: &Game, path: &Option<String>) {
+    let (Some(path), Some(recorder)) = (path, game.recorder()) else { return; };
+    if let Err(error) = recorder.replay().save(Path::new(path)) {
+        println!("{} {}", game.render.paint(game.render.text().save_replay_failed, Style::new().red()), error);
+    }
+}
+
+// writes the score after every move as csv, if asked for
+fn save_stats(game: &Game, path: &Option<String>) {
+    let Some(path) = path else { return; };
+    if let Err(error) = std::fs::write(path, game.stats_csv()) {
+        println!("{} {}", game.render.paint(game.render.text().save_stats_failed, Style::new().red()), error);
+    }
+}
+
+// Ctrl-C or SIGINT, saves everything quitting would and the game itself, so `--resume` can continue it
+fn save_interrupted(game: &Game, record_path: &Option<String>, stats_path: &Option<String>) {
+    save_high_score(game);
+    save_recording(game, record_path);
+    save_stats(game, stats_path);
+    match game.save_to_path(Path::new(RECOVERY_PATH)) {
+        Ok(()) => status(game, game.render.paint((game.render.text().interrupted)(RECOVERY_PATH), Style::new().yellow())),
+        Err(error) => println!("{} {}", game.render.paint(game.render.text().save_interrupted_failed, Style::new().red()), error)
+    }
+}
+
+// asks whether to continue the interrupted game, `false` when there's none or the answer is no
+fn offer_recovery(render: RenderOptions) -> Result<bool, Box<dyn Error>> {
+    if Path::new(RECOVERY_PATH).exists() == false {
+        println!("{}", render.paint(render.text().no_recovery, Style::new().yellow()));
+        return Ok(false);
+    }
+
+    println!("{}", render.paint(render.text().resume_prompt, Style::new().cyan().bold()));
+    let answer: Event = Input::blocking().next()?;
+    return Ok(matches!(answer, Event::Key(Key::Char('y' | 'Y'))));
+}
+
+// steps through a recorded game, the seed & config recreate the exact same boards
+fn play_replay(path: &str, render: RenderOptions) -> Result<(), Box<dyn Error>> {
+    let replay: Replay = Replay::load(Path::new(path))?;
+    let mut game: Game = Game::new_game_seeded(Some(replay.config.clone()), replay.seed)?;
+    game.render = render;
+    game.display_game()?;
+
+    for keypress in replay.keypresses() {
+        std::thread::sleep(REPLAY_MOVE_DELAY);
+        match game.apply_move(keypress) {
+            GameResult::Exit | GameResult::Reset => break,
+            _ => {}
+        }
+        game.animate_last_move();
+        game.display_game()?;
+        status(&game, format!("{}: {}", game.render.paint(game.render.text().replay_move, Style::new().green()), game.render.text().keypress(keypress)));
+    }
+
+    status(&game, game.render.paint(game.render.text().replay_finished, Style::new().green()));
+    return Ok(());
+}
+
+// lets `solver` play until it gets stuck, the quit key stops it early
+// `stop_on_win` ends the run as soon as the target tile is reached
+fn play_solver(mut game: Game, key_bindings: &KeyBindings, solver: impl Fn(&Game) -> Option<Keypress>, stop_on_win: bool) -> Result<(), Box<dyn Error>> {
+    let input: Input = Input::ticking(AI_MOVE_DELAY);
+
+    loop {
+        if let Event::Key(key) = input.next()? {
+            if key_bindings.resolve(key) == Ok(Keypress::Quit) {
+                status(&game, game.render.paint(game.render.text().stopped, Style::new().yellow()));
+                break;
+            }
+            continue;
+        }
+
+        let Some(keypress) = solver(&game) else {
+            status(&game, game.render.paint(game.render.text().game_over, Style::new().red()));
+            break;
+        };
+        let game_result: GameResult = game.apply_move(keypress);
+        game.animate_last_move();
+        game.display_game()?;
+        status(&game, format!("{}: {}", game.render.paint(game.render.text().ai_move, Style::new().green()), game.render.text().keypress(keypress)));
+
+        if stop_on_win == true && matches!(game_result, GameResult::Won) {
+            status(&game, game.render.paint((game.render.text().reached)(game.config.target), Style::new().yellow().bold()));
+            break;
+        }
+    }
+
+    print_summary(&game);
+    return Ok(());
+}
+
+// every character piped into stdin is a keypress through the key bindings, whitespace is skipped
+// the game ends when the input runs out, e.g. `echo wasdwasd | game_2048 --stdin`
+fn play_stdin(mut game: Game, sticky_seed: bool, record_path: &Option<String>, stats_path: &Option<String>) -> Result<(), Box<dyn Error>> {
+    'input: for line in std::io::stdin().lock().lines() {
+        for character in line?.chars().filter(|character| character.is_whitespace() == false) {
+            let game_result: GameResult = game.play_key(Key::Char(character));
+            match game_result {
+                GameResult::Exit => break 'input,
+                GameResult::Reset => game.reset(sticky_seed == false)?,
+                GameResult::Save => game.save_to_path(Path::new(SAVE_PATH))?,
+                _ => game.animate_last_move()
+            }
+            game.display_game()?;
+
+            match game_result {
+                GameResult::GameOver | GameResult::TimeUp => {
+                    status(&game, game.render.paint(game.render.text().game_over, Style::new().red()));
+                    break 'input;
+                },
+                GameResult::NextMove { summary, .. } => status(&game, game.render.paint(merge_message(game.render.text(), &summary, game.config.base), Style::new().green())),
+                GameResult::UnknownKeyPress => status(&game, game.render.paint((game.render.text().invalid_key_named)(character), Style::new().red())),
+                _ => status(&game, format!("{}: {:?}", game.render.paint(game.render.text().key, Style::new().green()), character))
+            }
+        }
+    }
+
+    print_summary(&game);
+    save_recording(&game, record_path);
+    save_stats(&game, stats_path);
+    return Ok(());
+}
+
+// every keypress moves all boards, until each of them is stuck
+fn play_multi(boards: usize, config: BoardConfig, render: RenderOptions, glyphs: &TileGlyphs, key_bindings: &KeyBindings) -> Result<(), Box<dyn Error>> {
+    let mut multi: MultiGame = MultiGame::new(boards, config.clone())?;
+    multi.set_render(render);
+    multi.set_glyphs(glyphs);
+    multi.set_key_bindings(key_bindings);
+    multi.display_game()?;
+
+    let input: Input = Input::blocking();
+    loop {
+        if multi.is_game_over() == true {
+            println!("{}", render.paint(render.text().game_over, Style::new().red()));
+            break;
+        }
+
+        let Event::Key(key) = input.next()? else { continue; };
+        let Ok(keypress) = key_bindings.resolve(key) else {
+            multi.display_game()?;
+            println!("{}", render.paint(render.text().invalid_key, Style::new().red()));
+            continue;
+        };
+
+        match multi.apply_move(keypress) {
+            GameResult::Exit => break,
+            GameResult::Reset => {
+                multi = MultiGame::new(boards, config.clone())?;
+                multi.set_render(render);
+                multi.set_glyphs(glyphs);
+                multi.display_game()?;
+            },
+            GameResult::NoMove => {
+                multi.display_game()?;
+                println!("{}", render.paint(render.text().unnecessary_move, Style::new().red()));
+            },
+            _ => multi.display_game()?
+        }
+    }
+
+    println!("{}: {}", render.paint(render.text().total_score, Style::new().green()), multi.score());
+    return Ok(());
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
-    let getch: Getch = Getch::new();
-    let config: BoardConfig;
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+
+    // https://no-color.org, any non-empty value turns the colors off
+    let no_color_env: bool = std::env::var_os("NO_COLOR").is_some_and(|value| value.is_empty() == false);
+    let no_color: bool = take_switch(&mut args, "--no-color") || no_color_env;
 
-    let should_print_usage: bool = std::env::args().map(|x| x.trim().to_lowercase()).any(|x| x == "--help" || x == "-h");
+    let should_print_usage: bool = args.iter().map(|x| x.trim().to_lowercase()).any(|x| x == "--help" || x == "-h");
     if should_print_usage {
-        print_usage();
+        print_usage(no_color == false);
         return Ok(());
     }
 
-    let args: Vec<String> = std::env::args().skip(1).take(3).collect();
-    if args.len() == 0 {
-        config = BoardConfig::default();
+    // the config file only fills in what the command line leaves out
+    let settings: Settings = match take_flag(&mut args, "--config") {
+        Some(path) => Settings::load(Path::new(&path))?,
+        None => Settings::discover()?
+    };
+    let color: bool = no_color == false && settings.color != Some(false);
+
+    let autoplay: bool = take_switch(&mut args, "--autoplay");
+    let piped: bool = take_switch(&mut args, "--stdin");
+    let ai_depth: Option<usize> = take_parsed(&mut args, "--ai-depth")?;
+    let load_path: Option<String> = take_flag(&mut args, "--load");
+    let resume: bool = take_switch(&mut args, "--resume");
+    let board_path: Option<String> = take_flag(&mut args, "--board");
+    let import_code: Option<String> = take_flag(&mut args, "--import");
+    let seed: Option<u64> = take_parsed(&mut args, "--seed")?;
+    let daily: Option<u64> = if take_switch(&mut args, "--daily") { Some(daily_seed()) } else { None };
+    let seed: Option<u64> = daily.or(seed);
+    let animate: bool = take_switch(&mut args, "--animate");
+    let animate_frames: usize = take_parsed(&mut args, "--animate-frames")?.unwrap_or(ANIMATION_FRAMES);
+    let frame_delay: Duration = take_parsed(&mut args, "--frame-delay")?.map_or(ANIMATION_FRAME_DELAY, Duration::from_millis);
+    let lang: Lang = match take_flag(&mut args, "--lang") {
+        Some(name) => Lang::from_name(&name).ok_or_else(|| format!("Unknown language: {}, try en or de", name))?,
+        None => Lang::default()
+    };
+    let theme: Theme = match take_flag(&mut args, "--theme") {
+        Some(name) => Theme::from_name(&name).ok_or_else(|| format!("Unknown theme: {}, try classic, ocean or mono", name))?,
+        None => settings.theme()?.unwrap_or_default()
+    };
+    let empty: Option<char> = match take_flag(&mut args, "--empty-glyph") {
+        Some(glyph) => {
+            let mut chars = glyph.chars();
+            match (chars.next(), chars.next()) {
+                (Some(empty), None) => Some(empty),
+                _ => return Err(format!("Empty glyph must be a single character, got '{}'", glyph).into())
+            }
+        },
+        None => None
+    };
+    let render: RenderOptions = RenderOptions {
+        ascii: take_switch(&mut args, "--ascii"),
+        json: take_switch(&mut args, "--json"),
+        animate_frames: if animate { animate_frames } else { 0 },
+        frame_delay,
+        merge_flash: if take_switch(&mut args, "--flash") { Some(MERGE_FLASH_DURATION) } else { None },
+        color,
+        exponent: take_switch(&mut args, "--exponent"),
+        theme,
+        clear: take_switch(&mut args, "--no-clear") == false && std::io::stdout().is_terminal(), // redirected output stays readable
+        empty,
+        lang
+    };
+    let record_path: Option<String> = take_flag(&mut args, "--record");
+    let stats_path: Option<String> = take_flag(&mut args, "--stats-out");
+    let boards: usize = take_parsed(&mut args, "--boards")?.unwrap_or(1);
+    let sticky_seed: bool = take_switch(&mut args, "--sticky-seed") || daily.is_some(); // there's only one daily game
+    let confirm_reset: bool = take_switch(&mut args, "--confirm-reset");
+    let no_spawn: bool = take_switch(&mut args, "--no-spawn");
+    let combo_scoring: bool = take_switch(&mut args, "--combo");
+    let gravity: bool = take_switch(&mut args, "--gravity");
+    let adaptive_spawn: bool = take_switch(&mut args, "--adaptive-spawn");
+    let wrap: bool = take_switch(&mut args, "--wrap");
+    let spawns: Option<usize> = take_parsed(&mut args, "--spawns")?;
+    let spawn_values: Option<Vec<(Tile, f64)>> = take_flag(&mut args, "--spawn-values").map(|text| parse_spawn_values(&text)).transpose()?;
+    let blockers: Option<usize> = take_parsed(&mut args, "--blockers")?;
+    let mode: Option<Mode> = take_flag(&mut args, "--mode")
+        .map(|name| Mode::from_name(&name).ok_or_else(|| format!("Unknown mode: {}, try normal or hardcore", name)))
+        .transpose()?;
+    let stop_at_target: bool = take_switch(&mut args, "--stop-at-target");
+    let target: Option<Tile> = take_parsed(&mut args, "--target")?;
+    let time_limit: Option<Duration> = take_parsed(&mut args, "--time-limit")?.map(Duration::from_secs);
+    let move_timer: Option<MoveTimer> = match take_seconds(&mut args, "--move-timer")? {
+        Some(start) => Some(MoveTimer {
+            start,
+            decay: take_parsed(&mut args, "--move-decay")?.unwrap_or(MOVE_TIMER_DECAY),
+            floor: take_seconds(&mut args, "--move-floor")?.unwrap_or(MOVE_TIMER_FLOOR),
+            penalty: if take_switch(&mut args, "--timeout-ends") { TimeoutPenalty::GameOver } else { TimeoutPenalty::Spawn }
+        }),
+        None => None
+    };
+    let mut key_bindings: KeyBindings = match take_flag(&mut args, "--keys") {
+        Some(path) => KeyBindings::load(Path::new(&path))?,
+        None => settings.key_bindings()?
+    };
+    if take_switch(&mut args, "--debug") {
+        key_bindings.bind(Keypress::Transpose, &[Key::Char('t'), Key::Char('T')]);
+    }
+    let glyphs: TileGlyphs = match take_flag(&mut args, "--glyphs") {
+        Some(path) => TileGlyphs::load(Path::new(&path))?,
+        None => TileGlyphs::default()
+    };
+    if let Some(path) = take_flag(&mut args, "--replay") {
+        return play_replay(&path, render);
+    }
+    if take_switch(&mut args, "--tutorial") {
+        return tutorial::run(&Input::blocking(), key_bindings, render);
+    }
+    args.truncate(3);
+
+    // switches only ever turn an option on (or spawning off), flags replace the file value when given
+    let mut config: BoardConfig = if args.is_empty() {
+        settings.board.clone()
     } else {
-        config = parse_args(&args).unwrap_or_default();
+        match parse_args(&args, settings.board.clone()) {
+            Ok(config) => config,
+            Err(message) => {
+                println!("{}", render.paint(format!("{}. Using default configuration.", message), Style::new().red()));
+                settings.board.clone()
+            }
+        }
+    };
+    config.time_limit = time_limit.or(config.time_limit);
+    config.move_timer = move_timer.or(config.move_timer);
+    config.blockers = blockers.unwrap_or(config.blockers);
+    config.combo_scoring |= combo_scoring;
+    config.spawn_on_move &= no_spawn == false;
+    config.spawn_per_move = spawns.unwrap_or(config.spawn_per_move);
+    config.gravity |= gravity;
+    config.adaptive_spawn |= adaptive_spawn;
+    config.wrap |= wrap;
+    config.mode = mode.unwrap_or(config.mode);
+    config.stop_at_target |= stop_at_target;
+    if let Some(target) = target {
+        config.target = target;
+    }
+    if let Some(spawn_values) = spawn_values {
+        config.spawn_mode = SpawnMode::Weighted;
+        config.spawn_values = spawn_values;
+    }
+
+    if boards > 1 {
+        return play_multi(boards, config, render, &glyphs, &key_bindings);
+    }
+
+    // a replay recreates its boards from the seed, so it has to start from a freshly dealt one
+    if record_path.is_some() && (load_path.is_some() || resume == true || import_code.is_some() || board_path.is_some()) {
+        return Err("--record only records new games, the replay can't start from a loaded, resumed, imported or given board".into());
     }
+    let resumed: bool = resume == true && offer_recovery(render)?;
+    let load_path: Option<String> = if resumed { Some(RECOVERY_PATH.to_string()) } else { load_path };
 
-    let mut game: Game = Game::new_game(Some(config))?;
+    let mut game: Game = match (load_path, import_code, board_path) {
+        (Some(path), _, _) => Game::load_from_path(Path::new(&path))?,
+        (None, Some(code), _) => Game::import_code(&code)?, // the code brings its own config
+        (None, None, Some(path)) => {
+            // the board decides the size, the rest of the config still applies
+            let board: Vec<Vec<Tile>> = read_board(&path)?;
+            config.height = board.len();
+            config.width = board.first().map_or(0, |row| row.len());
+            Game::from_board(board, config, 0)?
+        },
+        (None, None, None) => match seed {
+            Some(seed) => Game::new_game_seeded(Some(config), seed)?,
+            None => Game::new_game(Some(config))?
+        }
+    };
+    // a timeout spawns a tile without a keypress, so the replay couldn't play it back
+    if record_path.is_some() && game.config.move_timer.is_some() {
+        return Err("--record can't be combined with a move timer, the replay doesn't know when the time ran out".into());
+    }
+    if resumed == true {
+        std::fs::remove_file(RECOVERY_PATH)?; // only offered once
+    }
+    game.render = render;
+    game.key_bindings = key_bindings.clone();
+    game.glyphs = glyphs;
+    game.confirm_reset = confirm_reset;
+    game.load_high_score();
+    if record_path.is_some() {
+        game.start_recording();
+    }
     game.display_game()?;
+    if let Some(seed) = daily {
+        status(&game, format!("{}: {}", game.render.paint(game.render.text().daily, Style::new().cyan().bold()), seed));
+    }
+
+    if piped == true {
+        return play_stdin(game, sticky_seed, &record_path, &stats_path);
+    }
+    if let Some(depth) = ai_depth {
+        return play_solver(game, &key_bindings, |game| auto::expectimax(game, depth), false);
+    }
+    if autoplay {
+        return play_solver(game, &key_bindings, Game::suggest_move, true);
+    }
+
+    // the terminal is raw while playing so Ctrl-C arrives as a key, a SIGINT from elsewhere only raises the flag
+    let interrupted: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+    let flag: Arc<AtomicBool> = Arc::clone(&interrupted);
+    ctrlc::set_handler(move || flag.store(true, Ordering::SeqCst))?;
+
+    // never blocks on the keyboard, a timed game's clock has to run out & the interrupt flag has to be seen without any keypress
+    let input: Input = Input::ticking(if game.config.move_timer.is_some() { MOVE_TIMER_TICK } else { CLOCK_TICK });
+    let mut confirming: bool = false; // the last keypress asked to reset
+    let mut undo_offered: bool = false; // the game is lost, the next key either undoes the last move or ends it
+    let mut shown_clock: (Option<u64>, Option<u64>) = clock_seconds(&game);
 
     loop {
-        let game_result: GameResult = game.play_move(&getch)?;
+        let game_result: GameResult = if game.is_game_over() && game.can_undo() == false {
+            GameResult::GameOver
+        } else {
+            match input.next()? {
+                event if matches!(event, Event::Key(Key::Ctrl('c'))) || interrupted.load(Ordering::SeqCst) => {
+                    save_interrupted(&game, &record_path, &stats_path);
+                    break;
+                },
+                Event::Key(key) => game.play_key(key),
+                Event::Tick if game.time_up() => GameResult::TimeUp,
+                Event::Tick if game.move_time_left() == Some(Duration::ZERO) => game.move_timeout(),
+                Event::Tick => {
+                    // only the clock changed, json output stays one line per keypress
+                    let clock: (Option<u64>, Option<u64>) = clock_seconds(&game);
+                    if game.render.json == false && clock != shown_clock { game.display_game()?; }
+                    shown_clock = clock;
+                    continue;
+                }
+            }
+        };
+        let cancelled: bool = confirming == true && matches!(game_result, GameResult::NoMove);
+        confirming = matches!(game_result, GameResult::ConfirmReset);
         match game_result {
-            GameResult::Exit => { break; },
+            GameResult::Exit => {
+                print_summary(&game);
+                save_high_score(&game);
+                save_recording(&game, &record_path);
+                save_stats(&game, &stats_path);
+                break;
+            },
 
             GameResult::Reset => {
-                game = Game::new_game(Some(game.config))?;
+                save_high_score(&game);
+                save_recording(&game, &record_path);
+                save_stats(&game, &stats_path);
+                game.reset(sticky_seed == false)?;
+                game.load_high_score();
+                game.display_game()?;
+                if let Some(seed) = daily {
+                    status(&game, format!("{}: {}", game.render.paint(game.render.text().daily, Style::new().cyan().bold()), seed));
+                }
+            },
+
+            GameResult::ConfirmReset => {
+                game.display_game()?;
+                status(&game, game.render.paint(game.render.text().reset_prompt, Style::new().cyan().bold()));
+            },
+
+            GameResult::Save => {
+                game.save_to_path(Path::new(SAVE_PATH))?;
                 game.display_game()?;
+                status(&game, game.render.paint((game.render.text().saved)(SAVE_PATH), Style::new().green()));
+            },
+
+            GameResult::GameOver if game.config.stop_at_target && game.has_won() => {
+                game.animate_last_move();
+                game.display_game()?;
+                status(&game, game.render.paint(format!("{} {}", game.render.text().you_win, reached_target(&game)), Style::new().black().on_green().bold()));
+                print_summary(&game);
+                save_high_score(&game);
+                save_recording(&game, &record_path);
+                save_stats(&game, &stats_path);
+                break;
+            },
+
+            GameResult::GameOver if game.can_undo() && undo_offered == false => {
+                undo_offered = true;
+                game.animate_last_move();
+                game.display_game()?;
+                let undo: String = game.render.key_label(&game.key_bindings, Keypress::Undo);
+                status(&game, format!("{} {}", game.render.paint(game.render.text().game_over, Style::new().red()), (game.render.text().undo_or_quit)(&undo)));
             },
 
             GameResult::GameOver => {
                 game.display_game()?;
-                println!("{}", "--- Game Over ---".red());
+                status(&game, game.render.paint(game.render.text().game_over, Style::new().red()));
+                print_summary(&game);
+                save_high_score(&game);
+                save_recording(&game, &record_path);
+                save_stats(&game, &stats_path);
                 break;
             },
 
-            GameResult::NextMove => {
+            GameResult::TimeUp => {
+                game.display_game()?;
+                status(&game, game.render.paint(game.render.text().time_up, Style::new().red()));
+                print_summary(&game);
+                save_high_score(&game);
+                save_recording(&game, &record_path);
+                save_stats(&game, &stats_path);
+                break;
+            },
+
+            GameResult::Won => {
+                game.animate_last_move();
+                game.display_game()?;
+                status(&game, game.render.paint(format!("--- {} {} ---", reached_target(&game), game.render.text().keep_going), Style::new().yellow().bold()));
+            },
+
+            GameResult::NextMove { summary, .. } => {
+                game.animate_last_move();
+                game.display_game()?;
+                status(&game, game.render.paint(merge_message(game.render.text(), &summary, game.config.base), Style::new().green()));
+            },
+
+            GameResult::Undone => {
+                undo_offered = false;
+                game.display_game()?;
+                status(&game, game.render.paint(game.render.text().undone, Style::new().yellow()));
+            },
+
+            GameResult::Resized => {
+                game.load_high_score(); // scores are kept per board size
+                game.display_game()?;
+                status(&game, game.render.paint((game.render.text().resized)(game.config.width, game.config.height), Style::new().yellow()));
+            },
+
+            GameResult::Paused => {
                 game.display_game()?;
-                println!("{}", "--- Nice Move ---".green());
+                status(&game, game.render.paint(game.render.text().paused, Style::new().yellow()));
+            },
+
+            GameResult::Resumed => {
+                game.display_game()?;
+                status(&game, game.render.paint(game.render.text().resumed, Style::new().green()));
+            },
+
+            GameResult::SandboxEntered => {
+                game.display_game()?;
+                status(&game, game.render.paint(game.render.text().sandbox_entered, Style::new().cyan()));
+            },
+
+            GameResult::SandboxLeft => {
+                game.display_game()?;
+                status(&game, game.render.paint(game.render.text().sandbox_left, Style::new().green()));
+            },
+
+            GameResult::Edited => game.display_game()?,
+
+            GameResult::TimedOut { .. } => {
+                game.display_game()?;
+                status(&game, game.render.paint(game.render.text().too_slow, Style::new().red()));
+            },
+
+            GameResult::Transposed => {
+                game.display_game()?;
+                status(&game, game.render.paint(game.render.text().transposed, Style::new().yellow()));
+            },
+
+            GameResult::Hint(keypress) => {
+                game.display_game()?;
+                status(&game, game.render.paint(format!("{} {}", game.render.text().hint, game.render.text().keypress(keypress)), Style::new().cyan()));
+            },
+
+            GameResult::Solution { moves, score } => {
+                game.display_game()?;
+                status(&game, game.render.paint((game.render.text().solution)(moves.len(), score), Style::new().cyan()));
+                status(&game, move_letters(&moves));
             },
 
             GameResult::UnknownKeyPress => {
                 game.display_game()?;
-                println!("{}", "--- Invalid key ---".red());
+                status(&game, game.render.paint(game.render.text().invalid_key, Style::new().red()));
             },
+            GameResult::NoMove if cancelled == true => {
+                game.display_game()?;
+                status(&game, game.render.paint(game.render.text().reset_cancelled, Style::new().yellow()));
+            },
+
             GameResult::NoMove => {
                 game.display_game()?;
-                println!("{}", "--- Unnecessary move ---".red());
+                status(&game, game.render.paint(game.render.text().unnecessary_move, Style::new().red()));
             }
         }
     }
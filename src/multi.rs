@@ -0,0 +1,165 @@
+use std::fmt;
+use owo_colors::Style;
+use unicode_width::UnicodeWidthStr;
+
+use crate::error::GameError;
+use crate::game::{BoardConfig, Game, GameResult, Keypress, MoveSummary, Position, Tile};
+use crate::keybindings::KeyBindings;
+use crate::render::{RenderOptions, TileGlyphs};
+
+// space between two boards drawn next to each other
+const BOARD_GAP: &str = "  ";
+
+// several independent boards played with the same keypresses
+#[derive(Debug, Clone)]
+pub struct MultiGame {
+    games: Vec<Game>
+}
+
+// display width of a terminal line, skipping the `ESC [ ... m` color sequences
+fn visible_width(line: &str) -> usize {
+    let mut visible: String = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(character) = chars.next() {
+        if character == '\u{1b}' {
+            for code in chars.by_ref() {
+                if code == 'm' { break; }
+            }
+            continue;
+        }
+        visible.push(character);
+    }
+
+    return visible.width();
+}
+
+impl MultiGame {
+    // `boards` games with the same config, each with its own random tiles
+    pub fn new(boards: usize, config: BoardConfig) -> Result<Self, GameError> {
+        let mut games: Vec<Game> = Vec::with_capacity(boards);
+        for _ in 0..boards {
+            games.push(Game::new_game(Some(config.clone()))?);
+        }
+
+        return Ok(Self { games });
+    }
+
+    pub fn games(&self) -> &[Game] {
+        return &self.games;
+    }
+
+    pub fn set_render(&mut self, render: RenderOptions) {
+        for game in self.games.iter_mut() {
+            game.render = render;
+        }
+    }
+
+    pub fn set_glyphs(&mut self, glyphs: &TileGlyphs) {
+        for game in self.games.iter_mut() {
+            game.glyphs = glyphs.clone();
+        }
+    }
+
+    // only shown in the controls, `MultiGame` gets its keypresses already resolved
+    pub fn set_key_bindings(&mut self, key_bindings: &KeyBindings) {
+        for game in self.games.iter_mut() {
+            game.key_bindings = key_bindings.clone();
+        }
+    }
+
+    pub fn score(&self) -> u64 {
+        return self.games.iter().map(|game| game.score()).sum();
+    }
+
+    // over once every board is stuck
+    pub fn is_game_over(&self) -> bool {
+        return self.games.iter().all(|game| game.is_game_over());
+    }
+
+    // sends a direction to every board that can still move
+    // `NextMove` sums up the merges of all boards, its spawned tile is the first moved board's, `games` has the rest
+    // undo, hints, save, pausing, resizing, transposing & the sandbox would leave the boards out of step, so they aren't available here
+    pub fn apply_move(&mut self, keypress: Keypress) -> GameResult {
+        match keypress {
+            Keypress::Quit => return GameResult::Exit,
+            Keypress::Reset => return GameResult::Reset,
+            Keypress::Undo | Keypress::Hint | Keypress::Solve | Keypress::Save | Keypress::Pause => return GameResult::NoMove,
+            Keypress::Wider | Keypress::Narrower | Keypress::Taller | Keypress::Shorter | Keypress::Transpose => return GameResult::NoMove,
+            Keypress::Sandbox | Keypress::Place(_) | Keypress::Skip => return GameResult::NoMove,
+            _ => {}
+        }
+
+        if self.is_game_over() == true { return GameResult::GameOver; }
+
+        let mut summary: MoveSummary = MoveSummary::default();
+        let mut spawned: Option<Option<(Position, Tile)>> = None; // tile of the first board that moved
+        let mut won: bool = false;
+        for game in self.games.iter_mut() {
+            if game.is_game_over() == true { continue; }
+
+            match game.apply_move(keypress) {
+                GameResult::Won => won = true,
+                GameResult::NextMove { summary: board_summary, spawned: board_spawned } => {
+                    summary.merges += board_summary.merges;
+                    summary.points += board_summary.points;
+                    summary.largest_merge = summary.largest_merge.max(board_summary.largest_merge);
+                    spawned = spawned.or(Some(board_spawned));
+                },
+                _ => {}
+            }
+        }
+
+        if won == true { return GameResult::Won; }
+        if let Some(spawned) = spawned { return GameResult::NextMove { summary, spawned }; }
+        return GameResult::NoMove;
+    }
+
+    // clears the screen, prints the controls and then the boards next to each other
+    pub fn display_game(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(first) = self.games.first() else { return Ok(()); };
+
+        // json mode keeps one line per board
+        if first.render.json == true {
+            for game in &self.games {
+                game.display_game()?;
+            }
+            return Ok(());
+        }
+
+        first.render.clear_screen();
+        first.render.print_controls(&first.key_bindings);
+        print!("{}", self);
+
+        return Ok(());
+    }
+}
+
+impl fmt::Display for MultiGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(first) = self.games.first() else { return Ok(()); };
+        let render: &RenderOptions = &first.render;
+
+        // every board as its own block of lines, padded to the widest line of the block
+        let blocks: Vec<Vec<String>> = self.games.iter().map(|game| game.table().to_string().lines().map(String::from).collect()).collect();
+        let widths: Vec<usize> = blocks.iter().map(|block| block.iter().map(|line| visible_width(line)).max().unwrap_or(0)).collect();
+        let height: usize = blocks.iter().map(|block| block.len()).max().unwrap_or(0);
+
+        for i in 0..height {
+            let mut line: String = String::new();
+            for (block, &width) in blocks.iter().zip(widths.iter()) {
+                let part: &str = block.get(i).map_or("", |part| part.as_str());
+                line.push_str(part);
+                line.push_str(&" ".repeat(width - visible_width(part)));
+                line.push_str(BOARD_GAP);
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        let scores: Vec<String> = self.games.iter().map(|game| game.score().to_string()).collect();
+        writeln!(f, "{}{} ({})",
+            render.paint(render.text().score, Style::new().underline()), render.paint(self.score(), Style::new().green().bold().underline()), scores.join(" + ")
+        )?;
+
+        return Ok(());
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use owo_colors::{OwoColorize, Style};
+use getch_rs::Key;
+
+use crate::game::{Keypress, Tile};
+use crate::keybindings::KeyBindings;
+use crate::lang::{Lang, Strings};
+
+// how a key is shown in the controls
+fn key_name(key: &Key, text: &Strings, ascii: bool) -> Option<String> {
+    match (key, ascii) {
+        (Key::Char(' '), _) => Some(String::from(text.space_key)),
+        (Key::Char(character), _) => Some(character.to_string()),
+        (Key::Up, false) => Some(String::from("↑")),
+        (Key::Down, false) => Some(String::from("↓")),
+        (Key::Left, false) => Some(String::from("←")),
+        (Key::Right, false) => Some(String::from("→")),
+        (Key::Up, true) => Some(String::from("Up")),
+        (Key::Down, true) => Some(String::from("Down")),
+        (Key::Left, true) => Some(String::from("Left")),
+        (Key::Right, true) => Some(String::from("Right")),
+        (Key::Esc, _) => Some(String::from("Esc")),
+        _ => None
+    }
+}
+
+// tile color palettes, for terminals where the classic colors are hard to read
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Theme {
+    #[default]
+    Classic,
+    Ocean,
+    Mono
+}
+
+impl Theme {
+    pub fn from_name(name: &str) -> Option<Theme> {
+        match name {
+            "classic" => Some(Theme::Classic),
+            "ocean" => Some(Theme::Ocean),
+            "mono" => Some(Theme::Mono),
+            _ => None
+        }
+    }
+}
+
+// text drawn instead of a tile's number, e.g. emoji, tiles without one keep their number
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TileGlyphs {
+    glyphs: HashMap<Tile, String>
+}
+
+impl TileGlyphs {
+    pub fn glyph(&self, value: Tile) -> Option<&str> {
+        return self.glyphs.get(&value).map(String::as_str);
+    }
+
+    pub fn set(&mut self, value: Tile, glyph: &str) {
+        self.glyphs.insert(value, glyph.to_string());
+    }
+
+    // one `VALUE = TEXT` line per tile, e.g. `2 = 🐣`, lines starting with `#` are comments
+    pub fn load(path: &Path) -> Result<TileGlyphs, Box<dyn Error>> {
+        let contents: String = fs::read_to_string(path)?;
+        let mut glyphs: TileGlyphs = TileGlyphs::default();
+
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') { continue; }
+
+            let (value, glyph) = line.split_once('=').ok_or_else(|| format!("Invalid glyph line: {}", line))?;
+            let value: Tile = value.trim().parse().map_err(|_| format!("Invalid glyph tile: {}", value.trim()))?;
+            if glyph.trim().is_empty() { return Err(format!("Missing glyph for tile {}", value).into()); }
+
+            glyphs.set(value, glyph.trim());
+        }
+
+        return Ok(glyphs);
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RenderOptions {
+    pub ascii: bool, // ascii table borders and no colors, for terminals that garble box drawing characters
+    pub json: bool, // the game state as a single json line instead of the table, for scripts
+    pub animate_frames: usize, // in-between frames drawn while tiles slide, 0 - no animation
+    pub frame_delay: Duration, // how long each in-between frame stays on screen
+    pub merge_flash: Option<Duration>, // merged tiles are highlighted this long after a move, `None` - no flash
+    pub color: bool, // `false` - no colors and no other escape codes either, e.g. for logging to a file
+    pub exponent: bool, // tiles show how many times the base was multiplied, 11 instead of 2048
+    pub clear: bool, // `false` - every frame is printed below the last one instead of clearing the screen first
+    pub theme: Theme, // tile colors
+    pub empty: Option<char>, // drawn dimmed in empty cells, e.g. `·`, `None` - empty cells stay blank
+    pub lang: Lang // language of the controls, board labels & messages
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            ascii: false,
+            json: false,
+            animate_frames: 0,
+            frame_delay: Duration::ZERO,
+            merge_flash: None,
+            color: true,
+            exponent: false,
+            clear: true,
+            theme: Theme::Classic,
+            empty: None,
+            lang: Lang::English
+        }
+    }
+}
+
+impl RenderOptions {
+    // tiles & text get colors, both ascii & no color mode turn them off
+    pub fn colored(&self) -> bool {
+        return self.color == true && self.ascii == false;
+    }
+
+    // text shown to the player, in the chosen language
+    pub fn text(&self) -> &'static Strings {
+        return self.lang.strings();
+    }
+
+    // `text` with `style` applied, or just the plain text when styling is off
+    pub fn paint(&self, text: impl Display, style: Style) -> String {
+        if self.colored() == false { return text.to_string(); }
+        return text.style(style).to_string();
+    }
+
+    // starts the next frame on a blank screen, no color & no clear mode just keep printing below
+    pub(crate) fn clear_screen(&self) {
+        if self.color == false || self.clear == false { return; }
+        println!("{}c", 27 as char); // clear (terminal) screen
+    }
+
+    // key help printed above the board
+    // every key bound to `action`, letters first, e.g. `W/↑`, empty when nothing is bound to it
+    pub fn key_label(&self, bindings: &KeyBindings, action: Keypress) -> String {
+        let mut names: Vec<String> = bindings.keys(action).iter().filter_map(|key| key_name(key, self.text(), self.ascii)).collect();
+        // a letter bound in both cases shows once, upper case
+        let both_cases: Vec<String> = names.iter().filter(|name| name.to_uppercase() != **name && names.contains(&name.to_uppercase())).cloned().collect();
+        names.retain(|name| both_cases.contains(name) == false);
+        names.sort_by_key(|name| (name.is_ascii() == false || name.len() > 1, name.clone()));
+        names.dedup();
+        return names.join("/");
+    }
+
+    // the keys come from `bindings`, so rebound keys show up as they are
+    pub(crate) fn print_controls(&self, bindings: &KeyBindings) {
+        let text: &Strings = self.text();
+        let key = |action: Keypress, style: Style| self.paint(self.key_label(bindings, action), style.bold());
+        let moves: Vec<String> = [Keypress::Up, Keypress::Left, Keypress::Down, Keypress::Right].into_iter().map(|direction| key(direction, Style::new().yellow())).collect();
+        println!("{} - {}", moves.join(", "), text.control_move);
+        println!("{} - {}", key(Keypress::Reset, Style::new().cyan()), text.control_reset);
+        println!("{} - {}", key(Keypress::Undo, Style::new().magenta()), text.control_undo);
+        println!("{} - {}", key(Keypress::Hint, Style::new().magenta()), text.control_hint);
+        println!("{} - {}", key(Keypress::Solve, Style::new().magenta()), text.control_solve);
+        println!("{} - {}", key(Keypress::Save, Style::new().magenta()), text.control_save);
+        println!("{} - {}", key(Keypress::Pause, Style::new().magenta()), text.control_pause);
+        println!("{} - {} {} {}", key(Keypress::Sandbox, Style::new().magenta()), text.control_sandbox, self.paint("0-9", Style::new().magenta().bold()), text.control_place);
+        println!("{}/{} - {}, {}/{} - {}", key(Keypress::Narrower, Style::new().blue()), key(Keypress::Wider, Style::new().blue()), text.control_width, key(Keypress::Shorter, Style::new().blue()), key(Keypress::Taller, Style::new().blue()), text.control_height);
+        println!("{} - {}", key(Keypress::Quit, Style::new().red()), text.control_quit);
+    }
+}
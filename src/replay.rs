@@ -0,0 +1,136 @@
+#[cfg(feature = "serde")]
+use std::{error::Error, fs, io, path::Path};
+use std::time::{Duration, Instant};
+
+use crate::game::{BoardConfig, Keypress};
+
+// everything needed to play a game again, the seed & config recreate the board, the moves drive it
+#[derive(Debug, Clone)]
+pub struct Replay {
+    pub seed: u64,
+    pub config: BoardConfig,
+    pub moves: Vec<(Duration, Keypress)> // time since the game started paired with the keypress
+}
+
+// one letter per keypress, independent of the key bindings
+#[cfg(feature = "serde")]
+fn encode_keypress(keypress: Keypress) -> char {
+    match keypress {
+        Keypress::Left => 'L',
+        Keypress::Right => 'R',
+        Keypress::Up => 'U',
+        Keypress::Down => 'D',
+        Keypress::Undo => 'Z',
+        Keypress::Hint => 'H',
+        Keypress::Solve => 'V',
+        Keypress::Save => 'S',
+        Keypress::Reset => 'N',
+        Keypress::Quit => 'Q',
+        Keypress::Wider => '>',
+        Keypress::Narrower => '<',
+        Keypress::Taller => '+',
+        Keypress::Shorter => '-',
+        Keypress::Pause => 'P',
+        Keypress::Transpose => 'T',
+        Keypress::Sandbox => 'B',
+        Keypress::Skip => 'K',
+        Keypress::Place(exponent) => (b'0' + exponent.min(9)) as char
+    }
+}
+
+#[cfg(feature = "serde")]
+fn decode_keypress(letter: &str) -> Option<Keypress> {
+    match letter {
+        "L" => Some(Keypress::Left),
+        "R" => Some(Keypress::Right),
+        "U" => Some(Keypress::Up),
+        "D" => Some(Keypress::Down),
+        "Z" => Some(Keypress::Undo),
+        "H" => Some(Keypress::Hint),
+        "V" => Some(Keypress::Solve),
+        "S" => Some(Keypress::Save),
+        "N" => Some(Keypress::Reset),
+        "Q" => Some(Keypress::Quit),
+        ">" => Some(Keypress::Wider),
+        "<" => Some(Keypress::Narrower),
+        "+" => Some(Keypress::Taller),
+        "-" => Some(Keypress::Shorter),
+        "P" => Some(Keypress::Pause),
+        "T" => Some(Keypress::Transpose),
+        "B" => Some(Keypress::Sandbox),
+        "K" => Some(Keypress::Skip),
+        _ => letter.parse::<u8>().ok().filter(|&exponent| exponent <= 9).map(Keypress::Place)
+    }
+}
+
+impl Replay {
+    pub fn keypresses(&self) -> Vec<Keypress> {
+        return self.moves.iter().map(|&(_, keypress)| keypress).collect();
+    }
+
+    // `seed N`, `config {json}`, then one `MILLISECONDS LETTER` line per keypress
+    #[cfg(feature = "serde")]
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut contents: String = format!("seed {}\nconfig {}\n", self.seed, serde_json::to_string(&self.config)?);
+        for (time, keypress) in &self.moves {
+            contents.push_str(&format!("{} {}\n", time.as_millis(), encode_keypress(*keypress)));
+        }
+
+        return fs::write(path, contents);
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn load(path: &Path) -> Result<Replay, Box<dyn Error>> {
+        let contents: String = fs::read_to_string(path)?;
+        let mut lines = contents.lines();
+
+        let seed: u64 = lines.next().and_then(|line| line.strip_prefix("seed ")).ok_or("Invalid replay: missing seed")?.parse()?;
+        let config: BoardConfig = serde_json::from_str(lines.next().and_then(|line| line.strip_prefix("config ")).ok_or("Invalid replay: missing config")?)?;
+
+        let mut moves: Vec<(Duration, Keypress)> = vec![];
+        for line in lines {
+            let (time, letter) = line.split_once(' ').ok_or_else(|| format!("Invalid replay line: {}", line))?;
+            let keypress: Keypress = decode_keypress(letter).ok_or_else(|| format!("Invalid replay keypress: {}", letter))?;
+            moves.push((Duration::from_millis(time.parse()?), keypress));
+        }
+
+        return Ok(Replay { seed, config, moves });
+    }
+}
+
+// collects the keypresses of a game as they're played
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    started: Instant,
+    replay: Replay
+}
+
+impl Recorder {
+    pub fn new(seed: u64, config: BoardConfig) -> Self {
+        return Self { started: Instant::now(), replay: Replay { seed, config, moves: vec![] } };
+    }
+
+    pub fn record(&mut self, keypress: Keypress) {
+        self.replay.moves.push((self.started.elapsed(), keypress));
+    }
+
+    pub fn replay(&self) -> &Replay {
+        return &self.replay;
+    }
+}
+
+// move indices where two replays diverge, paired with the move each replay made there
+// only the moves both replays have are compared, `trailing_moves` has the rest of the longer one
+pub fn diff_replays(a: &Replay, b: &Replay) -> Vec<(usize, Keypress, Keypress)> {
+    return a.keypresses().into_iter().zip(b.keypresses()).enumerate()
+        .filter(|(_, (move_a, move_b))| move_a != move_b)
+        .map(|(i, (move_a, move_b))| (i, move_a, move_b))
+        .collect();
+}
+
+// moves the longer replay made after the shorter one ended, with their indices, empty for equally long replays
+pub fn trailing_moves(a: &Replay, b: &Replay) -> Vec<(usize, Keypress)> {
+    let shorter: usize = a.moves.len().min(b.moves.len());
+    let longer: &Replay = if a.moves.len() > b.moves.len() { a } else { b };
+    return longer.keypresses().into_iter().enumerate().skip(shorter).collect();
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+
+use crate::game::BoardConfig;
+use crate::highscore;
+use crate::keybindings::KeyBindings;
+use crate::render::Theme;
+
+// `rust_2048/config.toml` in the config directory, see `Settings::discover`
+const CONFIG_DIR: &str = "rust_2048";
+const CONFIG_FILE: &str = "config.toml";
+
+// defaults from the config file, the command line still wins over every one of them
+// the board options sit at the top level, e.g.
+//     width = 5
+//     target = 4096
+//     theme = "ocean"
+//     color = false
+//     [keys]
+//     left = "h left"
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    #[serde(flatten)]
+    pub board: BoardConfig,
+    pub theme: Option<String>, // same names as `--theme`
+    pub color: Option<bool>, // `false` - same as `--no-color`
+    pub keys: HashMap<String, String>, // `ACTION = "KEY KEY ..."`, same as a line of the `--keys` file
+    #[serde(flatten)]
+    unknown: HashMap<String, toml::Value> // whatever `board` & the fields above didn't take, e.g. a misspelled key
+}
+
+impl Settings {
+    pub fn path() -> Option<PathBuf> {
+        return Some(highscore::config_dir()?.join(CONFIG_DIR).join(CONFIG_FILE));
+    }
+
+    // where the config file used to be, next to the high scores
+    fn legacy_path() -> Option<PathBuf> {
+        return highscore::path(CONFIG_FILE);
+    }
+
+    // an unknown key is an error, a typo would otherwise quietly leave the default in place
+    pub fn parse(text: &str) -> Result<Settings, Box<dyn Error>> {
+        let settings: Settings = toml::from_str(text)?;
+        let mut unknown: Vec<&String> = settings.unknown.keys().collect();
+        unknown.sort();
+        if let Some(key) = unknown.first() {
+            return Err(format!("Unknown config key: {}", key).into());
+        }
+
+        return Ok(settings);
+    }
+
+    pub fn load(path: &Path) -> Result<Settings, Box<dyn Error>> {
+        let contents: String = fs::read_to_string(path)?;
+        return Settings::parse(&contents).map_err(|error| format!("{}: {}", path.display(), error).into());
+    }
+
+    // the config file in the config directory, then the old one next to the high scores
+    // no file - no settings, a broken file is still an error
+    pub fn discover() -> Result<Settings, Box<dyn Error>> {
+        let found: Option<PathBuf> = [Settings::path(), Settings::legacy_path()].into_iter().flatten().find(|path| path.exists());
+        return match found {
+            Some(path) => Settings::load(&path),
+            None => Ok(Settings::default())
+        };
+    }
+
+    pub fn theme(&self) -> Result<Option<Theme>, String> {
+        return self.theme.as_deref()
+            .map(|name| Theme::from_name(name).ok_or_else(|| format!("Unknown theme: {}, try classic, ocean or mono", name)))
+            .transpose();
+    }
+
+    // the default bindings with the `[keys]` table on top
+    pub fn key_bindings(&self) -> Result<KeyBindings, String> {
+        let mut bindings: KeyBindings = KeyBindings::default();
+        for (action, keys) in &self.keys {
+            bindings.rebind(action, keys)?;
+        }
+
+        return Ok(bindings);
+    }
+}
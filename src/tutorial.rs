@@ -0,0 +1,144 @@
+use std::error::Error;
+use owo_colors::Style;
+use game_2048::game::{Game, GameResult, Keypress};
+use game_2048::keybindings::KeyBindings;
+use game_2048::input::{Input, Event};
+use game_2048::render::RenderOptions;
+use game_2048::lang::Strings;
+
+// moves in a row the last step asks for
+const SPACE_MOVES: u32 = 5;
+// empty cells that have to stay free during the last step
+const SPACE_EMPTY_CELLS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Step { FirstMerge, ReachEight, KeepSpace, Done }
+
+impl Step {
+    fn next(self) -> Step {
+        match self {
+            Step::FirstMerge => Step::ReachEight,
+            Step::ReachEight => Step::KeepSpace,
+            Step::KeepSpace | Step::Done => Step::Done
+        }
+    }
+
+    fn prompt(self, text: &Strings) -> String {
+        match self {
+            Step::FirstMerge => String::from(text.step_first_merge),
+            Step::ReachEight => String::from(text.step_reach_eight),
+            Step::KeepSpace => (text.step_keep_space)(SPACE_MOVES, SPACE_EMPTY_CELLS),
+            Step::Done => String::from(text.step_done)
+        }
+    }
+}
+
+struct Tutorial {
+    step: Step,
+    streak: u32 // moves in a row with enough empty cells, used by `Step::KeepSpace`
+}
+
+impl Tutorial {
+    // checks the board & the merges of a successful move, advances when the step is fulfilled
+    fn after_move(&mut self, game: &Game, merges: u32) {
+        let completed: bool = match self.step {
+            Step::FirstMerge => merges > 0,
+            Step::ReachEight => game.largest_tile() >= 8,
+            Step::KeepSpace => {
+                if game.empty_count() >= SPACE_EMPTY_CELLS { self.streak += 1; } else { self.streak = 0; }
+                self.streak >= SPACE_MOVES
+            },
+            Step::Done => false
+        };
+
+        if completed { self.advance(); }
+    }
+
+    fn advance(&mut self) {
+        self.step = self.step.next();
+        self.streak = 0;
+    }
+
+    fn display(&self, game: &Game, message: &str) -> Result<(), Box<dyn Error>> {
+        let text: &Strings = game.render.text();
+        game.display_game()?;
+        println!("{} {}", game.render.paint(text.tutorial, Style::new().cyan().bold()), self.step.prompt(text));
+        if self.step == Step::KeepSpace {
+            println!("{}{}/{}", game.render.paint(text.progress, Style::new().underline()), self.streak, SPACE_MOVES);
+        }
+        println!("{} - {}", game.render.paint(game.render.key_label(&game.key_bindings, Keypress::Skip), Style::new().magenta().bold()), text.skip_step);
+        if message.is_empty() == false {
+            println!("{}", message);
+        }
+
+        return Ok(());
+    }
+}
+
+// walks through the scripted steps, a fresh board is dealt when the player gets stuck or resets
+pub fn run(input: &Input, key_bindings: KeyBindings, render: RenderOptions) -> Result<(), Box<dyn Error>> {
+    let mut tutorial: Tutorial = Tutorial { step: Step::FirstMerge, streak: 0 };
+    let mut game: Game = Game::new_game(None)?;
+    game.render = render;
+    game.key_bindings = key_bindings;
+    tutorial.display(&game, "")?;
+
+    while tutorial.step != Step::Done {
+        let Event::Key(key) = input.next()? else { continue; };
+        let keypress: Keypress = match game.key_bindings.resolve(key) {
+            Ok(Keypress::Skip) => {
+                tutorial.advance();
+                tutorial.display(&game, &render.paint(render.text().step_skipped, Style::new().yellow()))?;
+                continue;
+            },
+            Ok(key) => key,
+            Err(_) => {
+                tutorial.display(&game, &render.paint(render.text().invalid_key, Style::new().red()))?;
+                continue;
+            }
+        };
+
+        let message: String = match game.apply_move(keypress) {
+            GameResult::Exit => return Ok(()),
+
+            GameResult::Reset | GameResult::ConfirmReset => {
+                game.reset(true)?;
+                tutorial.streak = 0;
+                String::new()
+            },
+
+            GameResult::GameOver | GameResult::TimeUp => {
+                game.reset(true)?;
+                tutorial.streak = 0;
+                render.paint(render.text().stuck, Style::new().red())
+            },
+
+            GameResult::Save => render.paint(render.text().saving_disabled, Style::new().yellow()),
+            GameResult::Hint(keypress) => render.paint(format!("{} {}", render.text().hint, render.text().keypress(keypress)), Style::new().cyan()),
+            GameResult::Solution { moves, .. } => render.paint((render.text().tutorial_solution)(moves.len()), Style::new().cyan()),
+            GameResult::Undone => render.paint(render.text().undone, Style::new().yellow()),
+            GameResult::Resized => render.paint(render.text().tutorial_resized, Style::new().yellow()),
+            GameResult::Paused => render.paint(render.text().paused, Style::new().yellow()),
+            GameResult::Resumed => render.paint(render.text().resumed, Style::new().green()),
+            GameResult::Transposed => render.paint(render.text().transposed, Style::new().yellow()),
+            GameResult::TimedOut { .. } => render.paint(render.text().too_slow, Style::new().red()),
+            GameResult::SandboxEntered | GameResult::Edited => render.paint(render.text().tutorial_sandbox, Style::new().cyan()),
+            GameResult::SandboxLeft => render.paint(render.text().tutorial_sandbox_left, Style::new().green()),
+            GameResult::NoMove => render.paint(render.text().unnecessary_move, Style::new().red()),
+            GameResult::UnknownKeyPress => render.paint(render.text().invalid_key, Style::new().red()),
+
+            GameResult::NextMove { summary, .. } => {
+                tutorial.after_move(&game, summary.merges);
+                render.paint(render.text().nice_move, Style::new().green())
+            },
+            GameResult::Won => {
+                tutorial.after_move(&game, 1); // the target tile only comes from a merge
+                render.paint(render.text().nice_move, Style::new().green())
+            }
+        };
+
+        tutorial.display(&game, &message)?;
+    }
+
+    return Ok(());
+}
@@ -0,0 +1,33 @@
+use game_2048::auto;
+use game_2048::game::{Game, Keypress, Tile};
+
+// seeded games both solvers play, so they face the same spawns as long as they make the same moves
+const SEEDS: [u64; 5] = [1, 2, 3, 4, 5];
+// enough moves for a 512, few enough that depth 3 finishes in reasonable time
+const MOVE_LIMIT: usize = 400;
+
+// plays `solver` until it's stuck or made `MOVE_LIMIT` moves, returns the largest tile it reached
+fn play_out(seed: u64, solver: impl Fn(&Game) -> Option<Keypress>) -> Tile {
+    let mut game: Game = Game::new_game_seeded(None, seed).unwrap();
+    for _ in 0..MOVE_LIMIT {
+        let Some(direction) = solver(&game) else { break; };
+        game.apply_move(direction);
+    }
+
+    return game.largest_tile();
+}
+
+fn average_largest_tile(solver: impl Fn(&Game) -> Option<Keypress>) -> f64 {
+    let total: Tile = SEEDS.into_iter().map(|seed| play_out(seed, &solver)).sum();
+    return total as f64 / SEEDS.len() as f64;
+}
+
+// slow, a minute in release, `cargo test --release --test auto -- --ignored` runs it
+#[test]
+#[ignore]
+fn expectimax_reaches_higher_tiles_than_greedy() {
+    let greedy: f64 = average_largest_tile(auto::greedy);
+    let expectimax: f64 = average_largest_tile(|game| auto::expectimax(game, 3));
+    println!("average largest tile, greedy: {}, expectimax depth 3: {}", greedy, expectimax);
+    assert!(expectimax > greedy, "expectimax {} <= greedy {}", expectimax, greedy);
+}
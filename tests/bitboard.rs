@@ -0,0 +1,154 @@
+use std::time::Duration;
+use game_2048::bitboard::BitBoard;
+use game_2048::game::{collapse_row, BoardConfig, Game, GameResult, Keypress, MergeResolution, MoveTimer, Tile, TimeoutPenalty, DIRECTIONS};
+use game_2048::grid::Grid;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+fn random_grid(rng: &mut StdRng) -> Grid {
+    let mut grid: Grid = Grid::new(4, 4);
+    for row in 0..4 {
+        for column in 0..4 {
+            // mostly small tiles, so neighbours often match
+            if rng.gen_bool(0.7) { grid.set(row, column, 1 << rng.gen_range(1..=4)); }
+        }
+    }
+    return grid;
+}
+
+// the regular move on a single board, through the public line collapse
+fn reference_shift(grid: &Grid, direction: Keypress) -> (Grid, u64) {
+    let mut result: Grid = grid.clone();
+    let mut score: u64 = 0;
+    for i in 0..4 {
+        let mut line: Vec<Tile> = match direction {
+            Keypress::Left | Keypress::Right => grid.row(i),
+            _ => grid.column(i)
+        };
+        let reversed: bool = direction == Keypress::Right || direction == Keypress::Down;
+        if reversed { line.reverse(); }
+
+        let (mut collapsed, gained) = collapse_row(&line, 2);
+        if reversed { collapsed.reverse(); }
+        score += gained;
+
+        match direction {
+            Keypress::Left | Keypress::Right => result.set_row(i, collapsed),
+            _ => result.set_column(i, &collapsed)
+        }
+    }
+    return (result, score);
+}
+
+#[test]
+fn packed_moves_match_the_line_collapse() {
+    let mut rng: StdRng = StdRng::seed_from_u64(300);
+
+    for _ in 0..5000 {
+        let grid: Grid = random_grid(&mut rng);
+        let packed: BitBoard = BitBoard::from_grid(&grid).unwrap();
+        assert_eq!(packed.to_grid(), grid);
+
+        for direction in DIRECTIONS {
+            let (expected, expected_score) = reference_shift(&grid, direction);
+            let (moved, score) = packed.shift(direction);
+            assert_eq!(moved.to_grid(), expected, "{:?} of {:?}", direction, grid);
+            assert_eq!(score, expected_score, "{:?} of {:?}", direction, grid);
+        }
+    }
+}
+
+#[test]
+fn searches_get_the_packed_board_of_classic_games() {
+    let config: BoardConfig = BoardConfig { spawn_on_move: false, ..Default::default() };
+    let game: Game = Game::new_game_seeded(Some(config), 300).unwrap();
+    let packed: BitBoard = game.bitboard().unwrap();
+
+    for direction in DIRECTIONS {
+        let mut next: Game = game.clone();
+        next.apply_move(direction);
+        let (moved, score) = packed.shift(direction);
+        assert_eq!(moved.to_grid().rows(), next.board());
+        assert_eq!(score, next.score());
+    }
+
+    let walls: BoardConfig = BoardConfig { blockers: 2, ..Default::default() };
+    assert_eq!(Game::new_game_seeded(Some(walls), 300).unwrap().bitboard(), None);
+}
+
+#[test]
+fn unpackable_grids_are_rejected() {
+    assert_eq!(BitBoard::from_grid(&Grid::new(5, 4)), None);
+
+    let mut grid: Grid = Grid::new(4, 4);
+    grid.set(0, 0, 1 << 15);
+    assert_eq!(BitBoard::from_grid(&grid), None);
+
+    grid.set(0, 0, 3);
+    assert_eq!(BitBoard::from_grid(&grid), None);
+}
+
+// plays the same random game twice, once through `apply_move` and once through `fast_apply`
+fn check_games(config: BoardConfig, rng: &mut StdRng) {
+    let seed: u64 = rng.gen();
+    let mut reference: Game = Game::new_game_seeded(Some(config.clone()), seed).unwrap();
+    let mut fast: Game = Game::new_game_seeded(Some(config), seed).unwrap();
+
+    for _ in 0..300 {
+        // mostly moves, with an occasional undo so the history is checked too
+        let keypress: Keypress = if rng.gen_bool(0.05) { Keypress::Undo } else { DIRECTIONS[rng.gen_range(0..DIRECTIONS.len())] };
+        let expected = reference.apply_move(keypress);
+        let result = fast.fast_apply(keypress);
+
+        assert_eq!(format!("{:?}", result), format!("{:?}", expected), "{:?} on {:?}", keypress, reference.board());
+        assert_eq!(fast.board(), reference.board());
+        assert_eq!(fast.score(), reference.score());
+        assert_eq!(fast.moves(), reference.moves());
+        assert_eq!(fast.streak(), reference.streak());
+    }
+}
+
+#[test]
+fn fast_games_match_regular_games() {
+    let mut rng: StdRng = StdRng::seed_from_u64(2048);
+    let configs: [BoardConfig; 4] = [
+        BoardConfig::default(),
+        BoardConfig { combo_scoring: true, ..Default::default() },
+        BoardConfig { merge_resolution: MergeResolution::HighestValue, four_chance: 0.5, ..Default::default() },
+        BoardConfig { target: 64, undo_depth: 0, ..Default::default() }
+    ];
+
+    for config in configs {
+        for _ in 0..50 {
+            check_games(config.clone(), &mut rng);
+        }
+    }
+}
+
+#[test]
+fn other_boards_fall_back_to_regular_moves() {
+    let mut rng: StdRng = StdRng::seed_from_u64(7);
+    let configs: [BoardConfig; 3] = [
+        BoardConfig { width: 5, height: 3, ..Default::default() },
+        BoardConfig { blockers: 2, ..Default::default() },
+        BoardConfig { merge_resolution: MergeResolution::LastWins, merge_budget: Some(5), ..Default::default() }
+    ];
+
+    for config in configs {
+        for _ in 0..20 {
+            check_games(config.clone(), &mut rng);
+        }
+    }
+}
+
+#[test]
+fn fast_apply_stops_when_the_move_timer_ended_the_game() {
+    let timer: MoveTimer = MoveTimer { start: Duration::from_secs(4), decay: 0.5, floor: Duration::from_secs(1), penalty: TimeoutPenalty::GameOver };
+    let config: BoardConfig = BoardConfig { move_timer: Some(timer), ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 0, 0, 0], vec![0; 4], vec![0; 4], vec![0; 4]], config, 0).unwrap();
+    game.move_timeout();
+
+    assert!(matches!(game.fast_apply(Keypress::Right), GameResult::GameOver));
+    assert_eq!(game.board(), [vec![2, 0, 0, 0], vec![0; 4], vec![0; 4], vec![0; 4]]);
+    assert_eq!(game.moves(), 0);
+}
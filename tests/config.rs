@@ -0,0 +1,226 @@
+use std::time::Duration;
+#[cfg(feature = "serde")]
+use getch_rs::Key;
+use game_2048::error::GameError;
+use game_2048::game::{BoardConfig, Game, GameResult, Keypress, Mode, MoveTimer, SpawnMode, TimeoutPenalty};
+#[cfg(feature = "serde")]
+use game_2048::keybindings::KeyBindings;
+#[cfg(feature = "serde")]
+use game_2048::render::Theme;
+#[cfg(feature = "serde")]
+use game_2048::settings::Settings;
+
+#[test]
+fn zero_dimensions_are_rejected() {
+    for (width, height) in [(0, 4), (4, 0), (0, 0)] {
+        let config: BoardConfig = BoardConfig { width, height, count: 2, ..Default::default() };
+        assert_eq!(Game::new_game(Some(config)).unwrap_err(), GameError::InvalidDimensions { width, height });
+    }
+}
+
+#[test]
+fn zero_dimensions_are_rejected_for_given_boards() {
+    let config: BoardConfig = BoardConfig { width: 0, height: 0, count: 2, ..Default::default() };
+    assert_eq!(Game::from_board(vec![], config, 0).unwrap_err(), GameError::InvalidDimensions { width: 0, height: 0 });
+}
+
+#[test]
+fn adaptive_spawn_starts_at_the_configured_chance() {
+    let config: BoardConfig = BoardConfig { adaptive_spawn: true, ..Default::default() };
+    let fresh: Game = Game::new_game(Some(config.clone())).unwrap();
+    assert_eq!(fresh.four_chance(), config.four_chance);
+
+    // 64 is 4 doublings past 4
+    let grown: Game = Game::from_board(vec![vec![64, 0, 0, 0], vec![0; 4], vec![0; 4], vec![0; 4]], config.clone(), 0).unwrap();
+    assert!((grown.four_chance() - (config.four_chance + 0.04)).abs() < 1e-9);
+
+    let capped: Game = Game::from_board(vec![vec![1 << 30, 0, 0, 0], vec![0; 4], vec![0; 4], vec![0; 4]], config, 0).unwrap();
+    assert_eq!(capped.four_chance(), 0.3);
+}
+
+#[test]
+fn resizing_keeps_the_tiles_from_the_top_left() {
+    let config: BoardConfig = BoardConfig { width: 3, height: 2, count: 1, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 4, 8], vec![16, 32, 64]], config, 0).unwrap();
+
+    game.resize(4, 3).unwrap();
+    assert_eq!(game.board(), &[vec![2, 4, 8, 0], vec![16, 32, 64, 0], vec![0, 0, 0, 0]]);
+    assert_eq!((game.config.width, game.config.height), (4, 3));
+
+    game.resize(2, 1).unwrap();
+    assert_eq!(game.board(), &[vec![2, 4]]);
+}
+
+#[test]
+fn resizing_rechecks_the_dimensions_and_count() {
+    let config: BoardConfig = BoardConfig { count: 2, blockers: 1, ..Default::default() };
+    let mut game: Game = Game::new_game(Some(config)).unwrap();
+
+    assert_eq!(game.resize(0, 4).unwrap_err(), GameError::InvalidDimensions { width: 0, height: 4 });
+    assert_eq!(game.resize(1, 2).unwrap_err(), GameError::Overflow { count: 3, capacity: 2 });
+    assert_eq!(game.resize(3, 1).unwrap_err(), GameError::FullBoard);
+    assert_eq!((game.config.width, game.config.height), (4, 4));
+
+    // one cell to spare is enough to deal a new board
+    game.resize(2, 2).unwrap();
+    game.reset(true).unwrap();
+}
+
+#[test]
+fn hardcore_mode_has_no_undo_and_more_fours() {
+    let config: BoardConfig = BoardConfig { width: 4, height: 1, count: 1, mode: Mode::Hardcore, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 2, 0, 0]], config, 0).unwrap();
+    assert_eq!(game.four_chance(), 0.2);
+
+    assert!(matches!(game.apply_move(Keypress::Left), GameResult::NextMove { .. }));
+    assert!(matches!(game.apply_move(Keypress::Undo), GameResult::NoMove));
+    assert_eq!(game.score(), 4);
+}
+
+#[test]
+fn target_has_to_be_a_power_of_the_base() {
+    for target in [0, 1, 1000, 2049] {
+        let config: BoardConfig = BoardConfig { target, ..Default::default() };
+        assert_eq!(Game::new_game(Some(config)).unwrap_err(), GameError::InvalidTarget(target));
+    }
+
+    let config: BoardConfig = BoardConfig { target: 512, ..Default::default() };
+    assert!(Game::new_game(Some(config)).is_ok());
+}
+
+#[test]
+fn builder_sets_only_what_it_is_given() {
+    let game: Game = Game::builder().width(5).height(3).count(4).seed(42).target(512).four_chance(0.5).build().unwrap();
+    assert_eq!((game.config.width, game.config.height, game.config.count), (5, 3, 4));
+    assert_eq!(game.config.target, 512);
+    assert_eq!(game.config.four_chance, 0.5);
+    assert_eq!(game.config.undo_depth, BoardConfig::default().undo_depth);
+    assert_eq!(game.seed(), 42);
+    assert_eq!(game.empty_count(), 5 * 3 - 4);
+}
+
+#[test]
+fn builder_checks_like_the_constructors() {
+    assert_eq!(Game::builder().width(0).build().unwrap_err(), GameError::InvalidDimensions { width: 0, height: 4 });
+    assert_eq!(Game::builder().four_chance(1.5).build().unwrap_err(), GameError::InvalidProbability(1.5));
+    assert_eq!(Game::builder().target(100).build().unwrap_err(), GameError::InvalidTarget(100));
+}
+
+#[test]
+fn spawn_values_are_checked() {
+    let weighted = |spawn_values: Vec<(u64, f64)>| BoardConfig { spawn_mode: SpawnMode::Weighted, spawn_values, ..Default::default() };
+
+    assert_eq!(Game::new_game(Some(weighted(vec![(2, 1.0), (6, 1.0)]))).unwrap_err(), GameError::InvalidTile(6));
+    assert_eq!(Game::new_game(Some(weighted(vec![(2, -1.0)]))).unwrap_err(), GameError::InvalidSpawnWeight(-1.0));
+    assert_eq!(Game::new_game(Some(weighted(vec![(2, 0.0), (4, 0.0)]))).unwrap_err(), GameError::InvalidSpawnWeight(0.0));
+    assert_eq!(Game::new_game(Some(weighted(vec![]))).unwrap_err(), GameError::InvalidSpawnWeight(0.0));
+    assert!(Game::new_game(Some(weighted(vec![(2, 0.0), (8, 3.0)]))).is_ok());
+}
+
+#[test]
+fn weighted_spawns_only_use_the_given_values() {
+    let config: BoardConfig = BoardConfig { width: 4, height: 4, count: 15, spawn_mode: SpawnMode::Weighted, spawn_values: vec![(8, 1.0), (32, 1.0), (2, 0.0)], ..Default::default() };
+    for seed in 0..20 {
+        let game: Game = Game::new_game_seeded(Some(config.clone()), seed).unwrap();
+        for &tile in game.board().iter().flatten().filter(|&&tile| tile != 0) {
+            assert!(tile == 8 || tile == 32, "seed {} spawned {}", seed, tile);
+        }
+    }
+}
+
+#[test]
+fn move_timer_decay_has_to_shrink_the_time() {
+    for decay in [0.0, -0.5, 1.5, f64::NAN] {
+        let timer: MoveTimer = MoveTimer { start: Duration::from_secs(3), decay, floor: Duration::ZERO, penalty: TimeoutPenalty::Spawn };
+        let config: BoardConfig = BoardConfig { move_timer: Some(timer), ..Default::default() };
+        assert!(matches!(Game::new_game(Some(config)).unwrap_err(), GameError::InvalidProbability(_)), "{}", decay);
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn config_file_fills_in_the_given_options_only() {
+    let settings: Settings = Settings::parse("width = 5\ntarget = 4096\ntheme = \"ocean\"\ncolor = false\n[keys]\nleft = \"h left\"\n").unwrap();
+    assert_eq!((settings.board.width, settings.board.height, settings.board.target), (5, 4, 4096));
+    assert_eq!(settings.board.count, BoardConfig::default().count);
+    assert_eq!(settings.theme(), Ok(Some(Theme::Ocean)));
+    assert_eq!(settings.color, Some(false));
+
+    let bindings: KeyBindings = settings.key_bindings().unwrap();
+    assert_eq!(bindings.resolve(Key::Char('h')), Ok(Keypress::Left));
+    assert!(bindings.resolve(Key::Char('a')).is_err()); // replaced, like in the `--keys` file
+    assert_eq!(bindings.resolve(Key::Char('w')), Ok(Keypress::Up));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn empty_config_file_is_the_default() {
+    let settings: Settings = Settings::parse("").unwrap();
+    assert_eq!(settings.board.width, BoardConfig::default().width);
+    assert_eq!(settings.theme(), Ok(None));
+    assert_eq!(settings.key_bindings().unwrap(), KeyBindings::default());
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn bad_config_file_values_are_rejected() {
+    assert!(Settings::parse("width = \"five\"").is_err());
+    assert!(Settings::parse("theme = \"neon\"").unwrap().theme().is_err());
+    assert!(Settings::parse("[keys]\njump = \"j\"").unwrap().key_bindings().is_err());
+    assert!(Settings::parse("[keys]\nleft = \"hjkl\"").unwrap().key_bindings().is_err());
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn config_file_lives_in_its_own_folder() {
+    if let Some(path) = Settings::path() {
+        assert!(path.ends_with("rust_2048/config.toml"), "{}", path.display());
+    }
+}
+
+#[test]
+fn match_max_spawns_start_at_the_base() {
+    let config: BoardConfig = BoardConfig { count: 2, spawn_mode: SpawnMode::MatchMax, ..Default::default() };
+    let game: Game = Game::new_game_seeded(Some(config), 11).unwrap();
+    let tiles: Vec<u64> = game.board().iter().flatten().copied().filter(|&tile| tile != 0).collect();
+    assert_eq!(tiles, [2, 2]);
+}
+
+#[test]
+fn match_max_spawns_the_largest_tile() {
+    let config: BoardConfig = BoardConfig { count: 1, spawn_mode: SpawnMode::MatchMax, ..Default::default() };
+    let mut game: Game = Game::new_game_seeded(Some(config), 11).unwrap();
+
+    // a 16 in the corner, the cursor starts there
+    game.apply_move(Keypress::Sandbox);
+    game.apply_move(Keypress::Place(4));
+    game.apply_move(Keypress::Sandbox);
+    assert_eq!(game.largest_tile(), 16);
+
+    let direction: Keypress = game.available_moves()[0];
+    assert!(matches!(game.apply_move(direction), GameResult::NextMove { spawned: Some((_, 16)), .. }));
+}
+
+#[test]
+fn certain_four_chance_spawns_only_fours() {
+    let config: BoardConfig = BoardConfig { count: 6, four_chance: 1.0, ..Default::default() };
+    let mut game: Game = Game::new_game_seeded(Some(config), 8).unwrap();
+    assert!(game.board().iter().flatten().all(|&tile| tile == 0 || tile == 4));
+
+    for _ in 0..20 {
+        let Some(&direction) = game.available_moves().first() else { break; };
+        match game.apply_move(direction) {
+            GameResult::NextMove { spawned: Some((_, value)), .. } => assert_eq!(value, 4),
+            result => panic!("expected a spawn, got {:?}", result)
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn misspelled_config_keys_are_rejected() {
+    let error: String = Settings::parse("widht = 5\nheight = 3").unwrap_err().to_string();
+    assert_eq!(error, "Unknown config key: widht");
+    assert!(Settings::parse("[keys]\nleft = \"h\"\n[colors]\ndark = true").is_err());
+    assert!(Settings::parse("width = 5\nstop_at_target = true").is_ok());
+}
@@ -0,0 +1,109 @@
+use game_2048::game::{BoardConfig, Game, GameResult, Keypress, Tile, WALL};
+
+const W: Tile = WALL;
+
+fn game(board: Vec<Vec<Tile>>) -> Game {
+    let config: BoardConfig = BoardConfig { width: board[0].len(), height: board.len(), count: 1, ..Default::default() };
+    return Game::from_board(board, config, 0).unwrap();
+}
+
+// the shortcut & the full direction scan have to agree with the moves that are actually available
+fn assert_over(board: Vec<Vec<Tile>>, over: bool) {
+    let game: Game = game(board);
+    assert_eq!(game.is_game_over(), over, "board {:?}", game.board());
+    assert_eq!(game.available_moves().is_empty(), over, "board {:?}", game.board());
+}
+
+#[test]
+fn full_board_without_pairs_is_stuck() {
+    assert_over(vec![vec![2, 4], vec![4, 2]], true);
+    assert_over(vec![vec![2, 4, 2, 4], vec![4, 2, 4, 2], vec![2, 4, 2, 4], vec![4, 2, 4, 2]], true);
+}
+
+#[test]
+fn full_board_with_a_pair_can_merge() {
+    // horizontal pair in the last row
+    assert_over(vec![vec![2, 4, 2, 4], vec![4, 2, 4, 2], vec![2, 4, 2, 4], vec![4, 2, 8, 8]], false);
+    // vertical pair in the first column
+    assert_over(vec![vec![2, 4, 2], vec![2, 8, 4], vec![4, 2, 8]], false);
+}
+
+#[test]
+fn empty_cell_next_to_a_tile_can_slide() {
+    assert_over(vec![vec![2, 4, 2], vec![4, 0, 4], vec![2, 4, 2]], false);
+    assert_over(vec![vec![0, 0, 0], vec![0, 0, 0], vec![0, 0, 2]], false);
+}
+
+#[test]
+fn empty_cells_behind_walls_dont_help() {
+    // every empty cell only touches walls, the tiles can't reach them
+    assert_over(vec![vec![W, 0], vec![2, W]], true);
+    assert_over(vec![vec![2, 4, W], vec![4, 2, W], vec![W, W, 0]], true);
+}
+
+#[test]
+fn walls_dont_block_a_pair() {
+    assert_over(vec![vec![2, 2, W], vec![4, 8, W], vec![W, W, 0]], false);
+}
+
+#[test]
+fn largest_tiles_stop_merging_instead_of_wrapping() {
+    let config: BoardConfig = BoardConfig { width: 2, height: 1, count: 1, target: 1 << 63, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![1 << 63, 1 << 63]], config, 0).unwrap();
+
+    assert!(game.is_game_over());
+    assert!(matches!(game.apply_move(Keypress::Left), GameResult::GameOver));
+    assert_eq!(game.board(), &[vec![1 << 63, 1 << 63]]);
+}
+
+#[test]
+fn tiles_past_the_old_u32_limit_keep_doubling() {
+    let config: BoardConfig = BoardConfig { width: 3, height: 1, count: 1, spawn_on_move: false, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![1 << 31, 1 << 31, 0]], config, 0).unwrap();
+
+    game.apply_move(Keypress::Left);
+    assert_eq!(game.board(), &[vec![1 << 32, 0, 0]]);
+    assert_eq!(game.score(), 1 << 32);
+}
+
+#[test]
+fn score_stops_at_the_limit() {
+    let config: BoardConfig = BoardConfig { width: 2, height: 1, count: 1, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 2]], config, u64::MAX - 1).unwrap();
+    assert!(game.score_capped() == false);
+
+    game.apply_move(Keypress::Left);
+    assert_eq!(game.score(), u64::MAX);
+    assert!(game.score_capped());
+}
+
+#[test]
+fn full_board_warns_while_moves_remain() {
+    assert!(game(vec![vec![2, 2], vec![4, 8]]).is_board_full());
+    assert!(game(vec![vec![2, 0], vec![4, 8]]).is_board_full() == false);
+    assert!(game(vec![vec![2, 2], vec![4, 8]]).to_string().contains("BOARD FULL"));
+    assert!(game(vec![vec![2, 4], vec![4, 2]]).to_string().contains("BOARD FULL") == false);
+    assert!(game(vec![vec![2, 0], vec![4, 8]]).to_string().contains("BOARD FULL") == false);
+}
+
+#[test]
+fn reaching_the_target_ends_the_game_when_asked() {
+    let config: BoardConfig = BoardConfig { width: 4, height: 1, count: 1, target: 8, stop_at_target: true, spawn_on_move: false, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![4, 4, 2, 0]], config, 0).unwrap();
+    assert!(game.is_game_over() == false);
+
+    assert!(matches!(game.apply_move(Keypress::Left), GameResult::GameOver));
+    assert!(game.has_won());
+    assert!(game.is_game_over());
+    assert!(matches!(game.apply_move(Keypress::Right), GameResult::GameOver));
+}
+
+#[test]
+fn reaching_the_target_plays_on_by_default() {
+    let config: BoardConfig = BoardConfig { width: 4, height: 1, count: 1, target: 8, spawn_on_move: false, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![4, 4, 2, 0]], config, 0).unwrap();
+
+    assert!(matches!(game.apply_move(Keypress::Left), GameResult::Won));
+    assert!(game.is_game_over() == false);
+    assert!(matches!(game.apply_move(Keypress::Right), GameResult::NextMove { .. }));
+}
@@ -0,0 +1,485 @@
+#[cfg(feature = "serde")]
+use std::path::PathBuf;
+use std::time::Duration;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use game_2048::error::GameError;
+use game_2048::game::{BoardConfig, Game, GameResult, Keypress, MoveTimer, TimeoutPenalty, WALL};
+use game_2048::lang::Lang;
+use unicode_width::UnicodeWidthStr;
+
+// left, down, right, up over and over, a common beginner pattern that keeps most moves legal
+const CYCLE: [Keypress; 4] = [Keypress::Left, Keypress::Down, Keypress::Right, Keypress::Up];
+
+fn play_cycle(game: &mut Game, moves: usize) {
+    for i in 0..moves {
+        game.apply_move(CYCLE[i % CYCLE.len()]);
+    }
+}
+
+#[test]
+fn seeded_game_deals_a_known_board() {
+    let game: Game = Game::new_game_seeded(None, 42).unwrap();
+    assert_eq!(game.board(), &[vec![0, 0, 0, 4], vec![0, 0, 0, 0], vec![0, 0, 2, 0], vec![0, 0, 0, 0]]);
+    assert_eq!(game.score(), 0);
+}
+
+#[test]
+fn seeded_game_plays_to_a_known_board_and_score() {
+    let mut game: Game = Game::new_game_seeded(None, 42).unwrap();
+    play_cycle(&mut game, 40);
+
+    assert_eq!(game.board(), &[vec![4, 2, 4, 2], vec![0, 8, 64, 8], vec![2, 0, 0, 2], vec![0, 0, 0, 0]]);
+    assert_eq!(game.score(), 336);
+    assert_eq!(game.moves(), 40);
+}
+
+#[test]
+fn same_seed_and_moves_give_the_same_game() {
+    let mut first: Game = Game::new_game_seeded(None, 7).unwrap();
+    let mut second: Game = Game::new_game_seeded(None, 7).unwrap();
+    play_cycle(&mut first, 100);
+    play_cycle(&mut second, 100);
+
+    assert_eq!(first.board(), second.board());
+    assert_eq!(first.score(), second.score());
+}
+
+#[test]
+fn filled_small_board_ends_the_game() {
+    let config: BoardConfig = BoardConfig { width: 2, height: 2, count: 1, ..Default::default() };
+    let mut game: Game = Game::new_game_seeded(Some(config), 7).unwrap();
+
+    // a 2x2 board runs out of space after a handful of moves
+    let mut result: GameResult = GameResult::NoMove;
+    for i in 0..100 {
+        result = game.apply_move(CYCLE[i % CYCLE.len()]);
+        if matches!(result, GameResult::GameOver) { break; }
+    }
+
+    assert!(matches!(result, GameResult::GameOver), "expected game over, board is {:?}", game.board());
+    assert!(game.is_game_over());
+    assert_eq!(game.empty_count(), 0);
+}
+
+#[test]
+fn stuck_board_refuses_every_move() {
+    let config: BoardConfig = BoardConfig { width: 2, height: 2, count: 1, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 4], vec![4, 2]], config, 0).unwrap();
+    assert!(game.is_game_over());
+    assert!(game.available_moves().is_empty());
+
+    for direction in CYCLE {
+        assert!(matches!(game.apply_move(direction), GameResult::GameOver));
+    }
+    assert_eq!(game.board(), &[vec![2, 4], vec![4, 2]]);
+}
+
+#[test]
+fn score_history_follows_every_successful_move() {
+    let mut game: Game = Game::new_game_seeded(None, 42).unwrap();
+    play_cycle(&mut game, 40);
+
+    let history: &[u64] = game.score_history();
+    assert_eq!(history.len(), game.moves() as usize);
+    assert_eq!(history.last(), Some(&game.score()));
+    assert!(history.windows(2).all(|pair| pair[0] <= pair[1]));
+
+    let csv: String = game.stats_csv();
+    assert_eq!(csv.lines().next(), Some("move,score,largest_tile"));
+    assert_eq!(csv.lines().last(), Some(format!("{},{},{}", game.moves(), game.score(), game.largest_tile()).as_str()));
+}
+
+#[test]
+fn paused_game_ignores_moves_until_resumed() {
+    let mut game: Game = Game::new_game_seeded(None, 42).unwrap();
+    let board: Vec<Vec<u64>> = game.board().to_vec();
+
+    assert!(matches!(game.apply_move(Keypress::Pause), GameResult::Paused));
+    assert!(game.is_paused());
+    for direction in CYCLE {
+        assert!(matches!(game.apply_move(direction), GameResult::Paused));
+        assert!(matches!(game.fast_apply(direction), GameResult::Paused));
+    }
+    assert_eq!(game.board(), board.as_slice());
+    assert_eq!(game.moves(), 0);
+    assert!(matches!(game.apply_move(Keypress::Quit), GameResult::Exit));
+
+    assert!(matches!(game.apply_move(Keypress::Pause), GameResult::Resumed));
+    play_cycle(&mut game, 4);
+    assert!(game.moves() > 0);
+}
+
+#[test]
+fn extra_spawns_stop_once_the_board_is_full() {
+    let config: BoardConfig = BoardConfig { width: 3, height: 1, count: 1, spawn_per_move: 2, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 2, 8]], config, 0).unwrap();
+
+    assert!(matches!(game.apply_move(Keypress::Left), GameResult::NextMove { spawned: Some(((0, 2), _)), .. }));
+    assert_eq!(game.empty_count(), 0);
+}
+
+#[test]
+fn every_move_spawns_the_configured_tiles() {
+    let config: BoardConfig = BoardConfig { width: 4, height: 1, count: 1, spawn_per_move: 2, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![0, 0, 0, 2]], config, 0).unwrap();
+
+    game.apply_move(Keypress::Left);
+    assert_eq!(game.empty_count(), 1);
+}
+
+#[test]
+fn last_spawn_is_the_tile_the_move_added() {
+    let mut game: Game = Game::new_game_seeded(None, 42).unwrap();
+    assert_eq!(game.last_spawn(), None);
+
+    let GameResult::NextMove { spawned, .. } = game.apply_move(Keypress::Left) else { panic!("expected a move") };
+    assert_eq!(game.last_spawn(), spawned);
+
+    game.apply_move(Keypress::Undo);
+    assert_eq!(game.last_spawn(), None);
+}
+
+#[test]
+fn solve_plays_a_copy_to_the_end() {
+    let mut game: Game = Game::new_game_seeded(None, 42).unwrap();
+    play_cycle(&mut game, 10);
+    let board: Vec<Vec<u64>> = game.board().to_vec();
+
+    let (moves, score) = game.solve();
+    assert_eq!(game.board(), board.as_slice());
+    assert_eq!(game.moves(), 10);
+
+    // same rng, so playing the moves on the game itself ends up in the same place
+    for &keypress in &moves {
+        game.apply_move(keypress);
+    }
+    assert_eq!(game.score(), score);
+    assert!(game.is_game_over());
+}
+
+#[test]
+fn share_code_restores_the_board() {
+    let config: BoardConfig = BoardConfig { width: 5, height: 3, count: 3, base: 3, target: 729, ..Default::default() };
+    let board: Vec<Vec<u64>> = vec![vec![3, 0, 9, 0, 27], vec![0, WALL, 0, 0, 0], vec![243, 0, 0, 0, 3]];
+    let game: Game = Game::from_board(board.clone(), config, 1234).unwrap();
+
+    let shared: Game = Game::import_code(&game.export_code()).unwrap();
+    assert_eq!(shared.board(), board);
+    assert_eq!(shared.score(), 1234);
+    assert_eq!((shared.config.width, shared.config.height, shared.config.count), (5, 3, 3));
+    assert_eq!((shared.config.base, shared.config.target, shared.config.blockers), (3, 729, 1));
+}
+
+#[test]
+fn share_code_of_a_played_game_stays_short() {
+    let mut game: Game = Game::new_game_seeded(None, 11).unwrap();
+    play_cycle(&mut game, 40);
+
+    let code: String = game.export_code();
+    assert!(code.len() < 40, "{}", code);
+    assert!(code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    assert_eq!(Game::import_code(&code).unwrap().board(), game.board());
+}
+
+#[test]
+fn broken_share_codes_are_rejected() {
+    let code: String = Game::new_game_seeded(None, 5).unwrap().export_code();
+    for broken in ["", "not a code!", "AAAA", &code[..code.len() - 2]] {
+        assert_eq!(Game::import_code(broken).unwrap_err(), GameError::InvalidCode, "{}", broken);
+    }
+}
+
+#[test]
+fn share_code_with_a_huge_tile_count_is_rejected() {
+    // version, 2x2, count = u64::MAX, base 2, target 2048, score 0, four walls
+    let mut bytes: Vec<u8> = vec![1, 2, 2];
+    bytes.extend([0xff; 9]);
+    bytes.extend([0x01, 2, 0x80, 0x10, 0]);
+    bytes.extend([u8::MAX; 4]);
+    let code: String = URL_SAFE_NO_PAD.encode(bytes);
+    assert_eq!(Game::import_code(&code).unwrap_err(), GameError::Overflow { count: usize::MAX, capacity: 4 });
+}
+
+#[test]
+fn sandbox_places_tiles_at_the_cursor() {
+    let config: BoardConfig = BoardConfig { width: 3, height: 3, count: 1, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 0, 0], vec![0, 0, 0], vec![0, 0, 0]], config, 0).unwrap();
+
+    assert!(matches!(game.apply_move(Keypress::Sandbox), GameResult::SandboxEntered));
+    assert_eq!(game.cursor(), Some((0, 0)));
+    for keypress in [Keypress::Right, Keypress::Right, Keypress::Right, Keypress::Down, Keypress::Place(3), Keypress::Left, Keypress::Up, Keypress::Place(0)] {
+        assert!(matches!(game.apply_move(keypress), GameResult::Edited));
+    }
+    assert!(matches!(game.apply_move(Keypress::Undo), GameResult::NoMove));
+    assert_eq!(game.board(), [vec![2, 0, 0], vec![0, 0, 8], vec![0, 0, 0]]);
+    assert_eq!(game.moves(), 0);
+
+    assert!(matches!(game.apply_move(Keypress::Sandbox), GameResult::SandboxLeft));
+    assert_eq!(game.cursor(), None);
+    assert!(matches!(game.apply_move(Keypress::Left), GameResult::NextMove { .. }));
+    assert_eq!(game.board()[1][0], 8);
+}
+
+#[test]
+fn sandbox_can_unstick_a_finished_game() {
+    let config: BoardConfig = BoardConfig { width: 2, height: 2, count: 1, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 4], vec![4, 2]], config, 0).unwrap();
+    assert!(matches!(game.apply_move(Keypress::Left), GameResult::GameOver));
+
+    game.apply_move(Keypress::Sandbox);
+    game.apply_move(Keypress::Place(2));
+    game.apply_move(Keypress::Sandbox);
+    assert!(matches!(game.apply_move(Keypress::Left), GameResult::NextMove { .. }));
+    assert_eq!(game.board()[0][0], 8);
+}
+
+#[test]
+fn empty_glyph_fills_only_the_empty_cells() {
+    let config: BoardConfig = BoardConfig { width: 3, height: 2, count: 1, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 0, 0], vec![0, 0, 4]], config, 0).unwrap();
+    assert!(game.to_string().contains('·') == false);
+
+    game.render.empty = Some('·');
+    assert_eq!(game.to_string().matches('·').count(), 4);
+}
+
+#[test]
+fn board_labels_follow_the_language() {
+    let mut game: Game = Game::new_game_seeded(None, 1).unwrap();
+    assert!(game.to_string().contains("Score: "));
+
+    game.render.lang = Lang::from_name("de").unwrap();
+    let board: String = game.to_string();
+    assert!(board.contains("Punkte: ") && board.contains("Züge: "));
+    assert!(board.contains("Score: ") == false);
+    assert_eq!(game.render.text().keypress(Keypress::Up), "Hoch");
+    assert!(Lang::from_name("xx").is_none());
+}
+
+fn move_timer(penalty: TimeoutPenalty) -> MoveTimer {
+    return MoveTimer { start: Duration::from_secs(4), decay: 0.5, floor: Duration::from_secs(1), penalty };
+}
+
+#[test]
+fn move_time_shrinks_down_to_the_floor() {
+    let config: BoardConfig = BoardConfig { width: 4, height: 1, count: 1, spawn_on_move: false, move_timer: Some(move_timer(TimeoutPenalty::Spawn)), ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 0, 0, 0]], config, 0).unwrap();
+
+    let mut times: Vec<Duration> = vec![];
+    for keypress in [Keypress::Right, Keypress::Left, Keypress::Right, Keypress::Left] {
+        times.push(game.move_time().unwrap());
+        game.apply_move(keypress);
+    }
+    assert_eq!(times, [4, 2, 1, 1].map(Duration::from_secs));
+    assert!(game.move_time_left().unwrap() <= Duration::from_secs(1));
+
+    game.apply_move(Keypress::Pause);
+    assert_eq!(game.move_time_left(), None);
+}
+
+#[test]
+fn running_out_of_time_spawns_one_penalty_tile() {
+    let config: BoardConfig = BoardConfig { width: 3, height: 1, count: 1, move_timer: Some(move_timer(TimeoutPenalty::Spawn)), ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 0, 0]], config, 0).unwrap();
+
+    let GameResult::TimedOut { spawned: (position, tile) } = game.move_timeout() else { panic!("expected a penalty tile") };
+    assert_eq!(game.board()[position.0][position.1], tile);
+    assert_eq!(game.empty_count(), 1);
+    assert_eq!(game.moves(), 0);
+
+    game.move_timeout();
+    assert!(matches!(game.move_timeout(), GameResult::GameOver), "no room for the penalty tile");
+    assert!(game.is_game_over());
+}
+
+#[test]
+fn running_out_of_time_can_end_the_game() {
+    let config: BoardConfig = BoardConfig { width: 3, height: 1, count: 1, move_timer: Some(move_timer(TimeoutPenalty::GameOver)), ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 0, 0]], config, 0).unwrap();
+
+    assert!(matches!(game.move_timeout(), GameResult::GameOver));
+    assert!(game.is_game_over());
+    assert_eq!(game.board(), [vec![2, 0, 0]]);
+}
+
+#[test]
+fn no_move_timer_no_penalty() {
+    let mut game: Game = Game::new_game_seeded(None, 2).unwrap();
+    assert_eq!(game.move_time(), None);
+    assert!(matches!(game.move_timeout(), GameResult::NoMove));
+}
+
+#[test]
+fn board_hash_only_looks_at_the_board() {
+    let config = |width: usize, height: usize| BoardConfig { width, height, count: 1, ..Default::default() };
+    let board: Vec<Vec<u64>> = vec![vec![2, 0, 4], vec![0, WALL, 8]];
+    let hash: u64 = Game::from_board(board.clone(), config(3, 2), 0).unwrap().board_hash();
+
+    assert_eq!(Game::from_board(board, config(3, 2), 500).unwrap().board_hash(), hash);
+    assert_ne!(Game::from_board(vec![vec![2, 0, 4], vec![0, WALL, 16]], config(3, 2), 0).unwrap().board_hash(), hash);
+    assert_ne!(Game::from_board(vec![vec![2, 0], vec![4, 0], vec![WALL, 8]], config(2, 3), 0).unwrap().board_hash(), hash);
+}
+
+#[test]
+fn board_hash_follows_the_moves() {
+    let mut game: Game = Game::new_game_seeded(None, 21).unwrap();
+    let mut twin: Game = Game::new_game_seeded(None, 21).unwrap();
+    play_cycle(&mut game, 30);
+    play_cycle(&mut twin, 30);
+    assert_eq!(game.board_hash(), twin.board_hash());
+
+    let (_, next) = game.successors().remove(0);
+    assert_ne!(next.board_hash(), game.board_hash());
+}
+
+#[test]
+fn games_are_equal_by_board_score_and_config() {
+    let config: BoardConfig = BoardConfig { width: 3, height: 2, count: 1, ..Default::default() };
+    let board: Vec<Vec<u64>> = vec![vec![2, 0, 4], vec![0, WALL, 8]];
+    let game: Game = Game::from_board(board.clone(), config.clone(), 0).unwrap();
+
+    // the rng & the rendering don't count
+    let mut twin: Game = Game::from_board(board.clone(), config.clone(), 0).unwrap();
+    twin.render.lang = Lang::German;
+    assert_eq!(game, twin);
+
+    assert_ne!(Game::from_board(board.clone(), config.clone(), 4).unwrap(), game);
+    assert_ne!(Game::from_board(board, BoardConfig { target: 64, ..config }, 0).unwrap(), game);
+}
+
+#[test]
+fn cloned_games_move_on_their_own() {
+    let game: Game = Game::new_game_seeded(None, 5).unwrap();
+    let mut lookahead: Game = game.clone();
+    assert_eq!(lookahead, game);
+
+    let before: Game = game.clone();
+    let direction: Keypress = lookahead.available_moves()[0];
+    lookahead.apply_move(direction);
+    assert_ne!(lookahead, game);
+    assert_eq!(game, before);
+}
+
+#[test]
+fn arrows_show_the_merges_of_each_move() {
+    let config: BoardConfig = BoardConfig { width: 4, height: 2, count: 1, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 2, 4, 4], vec![2, 0, 0, 8]], config, 0).unwrap();
+    assert_eq!(game.preview_merges(Keypress::Left), 2);
+    assert_eq!(game.preview_merges(Keypress::Right), 2);
+    assert_eq!(game.preview_merges(Keypress::Up), 1);
+    assert_eq!(game.preview_merges(Keypress::Down), 1);
+
+    game.render.ascii = true;
+    game.render.color = false;
+    assert!(game.to_string().contains("<2 >2 ^1 v1"));
+
+    // a slide without merges shows 0, a dead direction stays a dot
+    let config: BoardConfig = BoardConfig { width: 2, height: 2, count: 1, ..Default::default() };
+    let mut slide: Game = Game::from_board(vec![vec![0, 2], vec![0, 4]], config, 0).unwrap();
+    slide.render.ascii = true;
+    slide.render.color = false;
+    assert!(slide.to_string().contains("<0 . . ."));
+}
+
+#[test]
+fn tiles_only_slide_once_the_merge_budget_is_spent() {
+    let config: BoardConfig = BoardConfig { width: 4, height: 3, count: 1, merge_budget: Some(1), spawn_on_move: false, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 2, 0, 0], vec![0; 4], vec![0, 4, 0, 0]], config, 0).unwrap();
+
+    game.apply_move(Keypress::Left);
+    assert_eq!(game.board(), [vec![4, 0, 0, 0], vec![0; 4], vec![4, 0, 0, 0]]);
+    assert_eq!(game.score(), 4);
+
+    // the two 4s would merge, but the budget is used up
+    assert!(matches!(game.apply_move(Keypress::Up), GameResult::NextMove { .. }));
+    assert_eq!(game.board(), [vec![4, 0, 0, 0], vec![4, 0, 0, 0], vec![0; 4]]);
+    assert_eq!(game.score(), 4);
+}
+
+#[test]
+fn wide_glyphs_line_up_with_numbers() {
+    let config: BoardConfig = BoardConfig { width: 2, height: 2, count: 1, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 0], vec![1024, 0]], config, 0).unwrap();
+    game.glyphs.set(2, "🐣2");
+    game.render.ascii = true;
+    game.render.color = false;
+
+    let board: String = game.to_string();
+    let cell = |text: &str| -> usize {
+        let line: &str = board.lines().find(|line| line.contains(text)).unwrap();
+        return line.split('|').nth(1).unwrap().width();
+    };
+    assert_eq!(cell("🐣2"), cell("1024"));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn saved_games_load_mid_game() {
+    let config: BoardConfig = BoardConfig { width: 5, height: 3, target: 512, ..Default::default() };
+    let mut game: Game = Game::new_game_seeded(Some(config), 17).unwrap();
+    play_cycle(&mut game, 20);
+
+    let path: PathBuf = std::env::temp_dir().join(format!("game_2048_round_trip_{}.json", std::process::id()));
+    game.save_to_path(&path).unwrap();
+    let loaded: Game = Game::load_from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.board(), game.board());
+    assert_eq!(loaded.score(), game.score());
+    assert_eq!(loaded.config, game.config);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn saves_with_a_huge_tile_count_are_rejected() {
+    let game: Game = Game::new_game_seeded(None, 17).unwrap();
+    let mut save: serde_json::Value = serde_json::to_value(&game).unwrap();
+    save["config"]["count"] = serde_json::json!(usize::MAX);
+    save["config"]["blockers"] = serde_json::json!(1);
+    assert!(serde_json::from_value::<Game>(save).is_err());
+}
+
+#[test]
+fn controls_show_the_bound_keys() {
+    let mut game: Game = Game::new_game_seeded(None, 1).unwrap();
+    assert_eq!(game.render.key_label(&game.key_bindings, Keypress::Left), "A/←");
+    assert_eq!(game.render.key_label(&game.key_bindings, Keypress::Skip), "N");
+
+    game.key_bindings.rebind("left", "h").unwrap();
+    game.key_bindings.rebind("quit", "x esc").unwrap();
+    game.render.ascii = true;
+    assert_eq!(game.render.key_label(&game.key_bindings, Keypress::Left), "h");
+    assert_eq!(game.render.key_label(&game.key_bindings, Keypress::Hint), "H"); // `h` moved over to left
+    assert_eq!(game.render.key_label(&game.key_bindings, Keypress::Quit), "x/Esc");
+    assert_eq!(game.render.key_label(&game.key_bindings, Keypress::Up), "W/Up");
+}
+
+#[test]
+fn undo_takes_back_the_whole_move() {
+    let config: BoardConfig = BoardConfig { width: 4, height: 1, count: 1, target: 8, merge_budget: Some(1), spawn_on_move: false, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![4, 4, 0, 0]], config, 0).unwrap();
+    assert!(matches!(game.apply_move(Keypress::Left), GameResult::Won));
+    assert!(game.has_won());
+
+    assert!(matches!(game.apply_move(Keypress::Undo), GameResult::Undone));
+    assert_eq!(game.board(), [vec![4, 4, 0, 0]]);
+    assert_eq!((game.score(), game.moves()), (0, 0));
+    assert!(game.has_won() == false);
+
+    // the merge budget is back too, so the same move merges & wins again
+    assert!(matches!(game.apply_move(Keypress::Left), GameResult::Won));
+    assert_eq!(game.board(), [vec![8, 0, 0, 0]]);
+}
+
+#[test]
+fn the_move_that_lost_the_game_can_be_undone() {
+    let config: BoardConfig = BoardConfig { width: 2, height: 2, count: 1, four_chance: 1.0, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 4], vec![16, 0]], config, 0).unwrap();
+    game.apply_move(Keypress::Right);
+    assert_eq!(game.board(), [vec![2, 4], vec![4, 16]]);
+    assert!(game.is_game_over() && game.can_undo());
+
+    assert!(matches!(game.apply_move(Keypress::Undo), GameResult::Undone));
+    assert_eq!(game.board(), [vec![2, 4], vec![16, 0]]);
+    assert!(game.is_game_over() == false);
+}
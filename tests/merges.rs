@@ -0,0 +1,143 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use game_2048::game::{collapse_row, collapse_row_with, BoardConfig, Game, Keypress, MergeResolution, Position, Tile};
+use game_2048::grid::Grid;
+
+// moving right collapses the reversed row, same as the game does
+fn collapse_row_right(row: &[Tile]) -> (Vec<Tile>, u64) {
+    let reversed: Vec<Tile> = row.iter().rev().copied().collect();
+    let (mut line, score) = collapse_row(&reversed, 2);
+    line.reverse();
+    return (line, score);
+}
+
+#[test]
+fn odd_run_merges_the_pair_closest_to_the_edge() {
+    assert_eq!(collapse_row(&[2, 2, 2], 2), (vec![4, 2, 0], 4));
+    assert_eq!(collapse_row_right(&[2, 2, 2]), (vec![0, 2, 4], 4));
+    assert_eq!(collapse_row(&[2, 2, 2, 0], 2), (vec![4, 2, 0, 0], 4));
+}
+
+#[test]
+fn merged_tile_does_not_merge_again_in_the_same_move() {
+    assert_eq!(collapse_row(&[4, 4, 4, 4], 2), (vec![8, 8, 0, 0], 16));
+    assert_eq!(collapse_row_right(&[4, 4, 4, 4]), (vec![0, 0, 8, 8], 16));
+    assert_eq!(collapse_row(&[4, 4, 8, 0], 2), (vec![8, 8, 0, 0], 8));
+    assert_eq!(collapse_row(&[2, 2, 4, 8], 2), (vec![4, 4, 8, 0], 4));
+}
+
+#[test]
+fn gaps_do_not_prevent_merges() {
+    assert_eq!(collapse_row(&[2, 0, 0, 2], 2), (vec![4, 0, 0, 0], 4));
+    assert_eq!(collapse_row(&[0, 4, 0, 4, 4], 2), (vec![8, 4, 0, 0, 0], 8));
+}
+
+#[test]
+fn different_tiles_only_slide() {
+    assert_eq!(collapse_row(&[0, 2, 0, 4], 2), (vec![2, 4, 0, 0], 0));
+    assert_eq!(collapse_row(&[2, 4, 2, 4], 2), (vec![2, 4, 2, 4], 0));
+}
+
+// distinct tiles never merge, so these only check how the zeroes are moved out of the way
+#[test]
+fn compaction_handles_empty_and_all_zero_rows() {
+    assert_eq!(collapse_row(&[], 2), (vec![], 0));
+    assert_eq!(collapse_row_right(&[]), (vec![], 0));
+    assert_eq!(collapse_row(&[0, 0, 0, 0], 2), (vec![0, 0, 0, 0], 0));
+    assert_eq!(collapse_row_right(&[0, 0, 0, 0]), (vec![0, 0, 0, 0], 0));
+}
+
+#[test]
+fn compaction_keeps_already_compacted_rows() {
+    assert_eq!(collapse_row(&[2, 4, 8, 0], 2), (vec![2, 4, 8, 0], 0));
+    assert_eq!(collapse_row_right(&[0, 2, 4, 8]), (vec![0, 2, 4, 8], 0));
+
+    // no zeroes at all, nothing to move in either direction
+    assert_eq!(collapse_row(&[2, 4, 8, 16], 2), (vec![2, 4, 8, 16], 0));
+    assert_eq!(collapse_row_right(&[2, 4, 8, 16]), (vec![2, 4, 8, 16], 0));
+}
+
+#[test]
+fn compaction_keeps_the_tile_order() {
+    assert_eq!(collapse_row(&[0, 2, 0, 4, 0, 8], 2), (vec![2, 4, 8, 0, 0, 0], 0));
+    assert_eq!(collapse_row_right(&[0, 2, 0, 4, 0, 8]), (vec![0, 0, 0, 2, 4, 8], 0));
+    assert_eq!(collapse_row(&[16, 0, 0, 2], 2), (vec![16, 2, 0, 0], 0));
+    assert_eq!(collapse_row_right(&[16, 0, 0, 2]), (vec![0, 0, 16, 2], 0));
+    assert_eq!(collapse_row(&[0], 2), (vec![0], 0));
+    assert_eq!(collapse_row_right(&[2]), (vec![2], 0));
+}
+
+#[test]
+fn competing_pairs_merge_by_the_resolution_in_both_orientations() {
+    let cases: [(MergeResolution, bool, Vec<Tile>); 6] = [
+        (MergeResolution::FirstWins, false, vec![4, 2, 0]),
+        (MergeResolution::FirstWins, true, vec![0, 2, 4]),
+        (MergeResolution::LastWins, false, vec![2, 4, 0]),
+        (MergeResolution::LastWins, true, vec![0, 4, 2]),
+        (MergeResolution::HighestValue, false, vec![4, 2, 0]), // equal pairs fall back to `FirstWins`
+        (MergeResolution::HighestValue, true, vec![0, 2, 4])
+    ];
+
+    for (resolution, reverse, expected) in cases {
+        assert_eq!(collapse_row_with(&[2, 2, 2], 2, resolution, reverse), (expected, 4), "{:?} reversed: {}", resolution, reverse);
+    }
+}
+
+#[test]
+fn highest_value_pair_merges_first_from_either_side() {
+    assert_eq!(collapse_row_with(&[2, 2, 4, 4, 4], 2, MergeResolution::HighestValue, false), (vec![4, 8, 4, 0, 0], 12));
+    assert_eq!(collapse_row_with(&[4, 4, 4, 2, 2], 2, MergeResolution::HighestValue, true), (vec![0, 0, 4, 8, 4], 12));
+    assert_eq!(collapse_row_with(&[2, 2, 4, 4, 4], 2, MergeResolution::LastWins, false), (vec![4, 4, 8, 0, 0], 12));
+}
+
+#[test]
+fn unreversed_first_wins_is_the_classic_collapse() {
+    for row in [vec![2, 2, 2, 2], vec![0, 4, 4, 8], vec![2, 0, 2, 4, 4]] {
+        assert_eq!(collapse_row_with(&row, 2, MergeResolution::FirstWins, false), collapse_row(&row, 2));
+        assert_eq!(collapse_row_with(&row, 2, MergeResolution::FirstWins, true), collapse_row_right(&row));
+    }
+}
+
+#[test]
+fn ambiguous_rows_move_by_the_configured_resolution() {
+    let cases: [(MergeResolution, Vec<Tile>, Vec<Tile>); 3] = [
+        (MergeResolution::FirstWins, vec![4, 2, 0], vec![0, 2, 4]),
+        (MergeResolution::LastWins, vec![2, 4, 0], vec![0, 4, 2]),
+        (MergeResolution::HighestValue, vec![4, 2, 0], vec![0, 2, 4]) // equal pairs fall back to `FirstWins`
+    ];
+
+    for (merge_resolution, left, right) in cases {
+        let config: BoardConfig = BoardConfig { width: 3, height: 1, count: 1, merge_resolution, ..Default::default() };
+        let game: Game = Game::from_board(vec![vec![2, 2, 2]], config, 0).unwrap();
+        assert_eq!(game.preview(Keypress::Left), (Grid::from(vec![left]), 4), "{:?} left", merge_resolution);
+        assert_eq!(game.preview(Keypress::Right), (Grid::from(vec![right]), 4), "{:?} right", merge_resolution);
+    }
+}
+
+#[test]
+fn highest_value_pair_wins_in_the_game() {
+    let config: BoardConfig = BoardConfig { width: 5, height: 1, count: 1, merge_resolution: MergeResolution::HighestValue, ..Default::default() };
+    let game: Game = Game::from_board(vec![vec![2, 2, 4, 4, 4]], config, 0).unwrap();
+    assert_eq!(game.preview(Keypress::Left).0.rows(), &[vec![4, 8, 4, 0, 0]]);
+}
+
+// what the merge hook was called with, shared with the hook
+type Merges = Rc<RefCell<Vec<(Position, Tile)>>>;
+
+#[test]
+fn merge_hook_fires_once_per_merge_with_the_new_value() {
+    let config: BoardConfig = BoardConfig { width: 4, height: 2, count: 1, spawn_on_move: false, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 2, 4, 4], vec![8, 8, 0, 2]], config, 0).unwrap();
+    let merges: Merges = Rc::new(RefCell::new(vec![]));
+    let seen: Merges = Rc::clone(&merges);
+    game.set_on_merge(move |position, value| seen.borrow_mut().push((position, value)));
+
+    game.apply_move(Keypress::Left);
+    let mut merged: Vec<(Position, Tile)> = merges.borrow().clone();
+    merged.sort();
+    assert_eq!(merged, [((0, 0), 4), ((0, 1), 8), ((1, 0), 16)]);
+
+    // a move without merges doesn't call it
+    game.apply_move(Keypress::Right);
+    assert_eq!(merges.borrow().len(), 3);
+}
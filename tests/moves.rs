@@ -0,0 +1,133 @@
+use game_2048::error::GameError;
+use game_2048::game::{collapse_row, BoardConfig, Game, GameResult, Keypress, Tile, DIRECTIONS};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+// wide, tall, single row/column and sparse boards, where columns are often entirely zero
+const SHAPES: [(usize, usize, usize); 8] = [(6, 3, 4), (3, 6, 4), (8, 2, 1), (2, 8, 1), (1, 5, 2), (5, 1, 2), (4, 4, 2), (7, 3, 1)];
+
+fn column(board: &[Vec<Tile>], column: usize) -> Vec<Tile> {
+    return board.iter().map(|row| row[column]).collect();
+}
+
+fn tile_sum(board: &[Vec<Tile>]) -> u64 {
+    return board.iter().flatten().sum();
+}
+
+fn tile_count(board: &[Vec<Tile>]) -> usize {
+    return board.iter().flatten().filter(|&&tile| tile != 0).count();
+}
+
+// checks every vertical successor of `game` against the pure row collapse
+fn check_vertical_moves(game: &Game) {
+    let before: &[Vec<Tile>] = game.board();
+    let width: usize = game.config.width;
+
+    for (direction, next) in game.successors() {
+        let after: &[Vec<Tile>] = next.board();
+        if direction != Keypress::Up && direction != Keypress::Down { continue; }
+
+        // merging two equal tiles keeps the sum, and tiles never appear out of nowhere
+        assert_eq!(tile_sum(before), tile_sum(after), "{:?} changed the tile sum of {:?}", direction, before);
+        assert!(tile_count(after) <= tile_count(before), "{:?} added tiles to {:?}", direction, before);
+
+        let mut expected_score: u64 = 0;
+        for i in 0..width {
+            let mut line: Vec<Tile> = column(before, i);
+            if direction == Keypress::Down { line.reverse(); }
+
+            let (mut expected, score) = collapse_row(&line, 2);
+            if direction == Keypress::Down { expected.reverse(); }
+
+            assert_eq!(column(after, i), expected, "{:?} column {} of {:?}", direction, i, before);
+            expected_score += score;
+        }
+        assert_eq!(next.score() - game.score(), expected_score, "{:?} scored wrong on {:?}", direction, before);
+    }
+}
+
+#[test]
+fn vertical_moves_preserve_tiles_on_rectangular_boards() {
+    let mut rng: StdRng = StdRng::seed_from_u64(2048);
+
+    for (width, height, count) in SHAPES {
+        for seed in 0..20 {
+            let config: BoardConfig = BoardConfig { width, height, count, ..Default::default() };
+            let mut game: Game = Game::new_game_seeded(Some(config), seed).unwrap();
+
+            for _ in 0..200 {
+                check_vertical_moves(&game);
+                game.apply_move(DIRECTIONS[rng.gen_range(0..DIRECTIONS.len())]);
+            }
+        }
+    }
+}
+
+#[test]
+fn preview_shows_the_move_without_changing_the_game() {
+    let config: BoardConfig = BoardConfig { width: 4, height: 1, count: 1, ..Default::default() };
+    let game: Game = Game::from_board(vec![vec![2, 2, 4, 0]], config, 10).unwrap();
+
+    let (board, score) = game.preview(Keypress::Left);
+    assert_eq!(board.rows(), &[vec![4, 4, 0, 0]]);
+    assert_eq!(score, 14);
+    assert_eq!(game.board(), &[vec![2, 2, 4, 0]]);
+    assert_eq!(game.score(), 10);
+    assert_eq!(game.moves(), 0);
+}
+
+#[test]
+fn preview_leaves_the_spawns_alone() {
+    let mut previewed: Game = Game::new_game_seeded(None, 9).unwrap();
+    let mut plain: Game = Game::new_game_seeded(None, 9).unwrap();
+
+    for i in 0..50 {
+        for direction in DIRECTIONS {
+            previewed.preview(direction);
+        }
+        previewed.apply_move(DIRECTIONS[i % DIRECTIONS.len()]);
+        plain.apply_move(DIRECTIONS[i % DIRECTIONS.len()]);
+    }
+
+    assert_eq!(previewed.board(), plain.board());
+}
+
+#[test]
+fn transposing_twice_changes_nothing() {
+    let mut game: Game = Game::new_game_seeded(Some(BoardConfig { width: 5, height: 5, count: 12, ..Default::default() }), 3).unwrap();
+    let board: Vec<Vec<Tile>> = game.board().to_vec();
+
+    game.transpose().unwrap();
+    assert_eq!(column(game.board(), 2), board[2]);
+    game.transpose().unwrap();
+    assert_eq!(game.board(), board);
+}
+
+#[test]
+fn transposed_left_matches_up() {
+    let mut game: Game = Game::from_board(vec![vec![2, 0, 4, 0], vec![2, 4, 4, 0], vec![0, 8, 0, 2], vec![0, 8, 2, 2]], BoardConfig::default(), 0).unwrap();
+    let (up, up_score) = game.preview(Keypress::Up);
+
+    game.transpose().unwrap();
+    let (left, left_score) = game.preview(Keypress::Left);
+    assert_eq!(left.cols(), up.rows());
+    assert_eq!(left_score, up_score);
+}
+
+#[test]
+fn only_square_boards_transpose() {
+    let config: BoardConfig = BoardConfig { width: 3, height: 2, count: 1, ..Default::default() };
+    let mut game: Game = Game::from_board(vec![vec![2, 0, 0], vec![0, 4, 0]], config, 0).unwrap();
+    assert_eq!(game.transpose().unwrap_err(), GameError::InvalidDimensions { width: 3, height: 2 });
+    assert!(matches!(game.apply_move(Keypress::Transpose), GameResult::NoMove));
+    assert_eq!(game.board(), [vec![2, 0, 0], vec![0, 4, 0]]);
+}
+
+#[test]
+fn legal_moves_mask_has_only_the_left_bit() {
+    // every tile already sits right & nothing merges, only left slides
+    let config: BoardConfig = BoardConfig { width: 3, height: 2, count: 1, ..Default::default() };
+    let game: Game = Game::from_board(vec![vec![0, 2, 4], vec![0, 4, 8]], config, 0).unwrap();
+    assert_eq!(game.legal_moves_mask(), 1 << 0);
+    assert_eq!(game.available_moves(), [Keypress::Left]);
+}
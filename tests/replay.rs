@@ -0,0 +1,26 @@
+use std::time::Duration;
+use game_2048::game::{BoardConfig, Keypress};
+use game_2048::replay::{diff_replays, trailing_moves, Replay};
+
+fn replay(keypresses: &[Keypress]) -> Replay {
+    let moves: Vec<(Duration, Keypress)> = keypresses.iter().enumerate().map(|(i, &keypress)| (Duration::from_millis(i as u64 * 100), keypress)).collect();
+    return Replay { seed: 7, config: BoardConfig::default(), moves };
+}
+
+#[test]
+fn replays_diverge_at_move_five() {
+    let shared: [Keypress; 5] = [Keypress::Left, Keypress::Up, Keypress::Up, Keypress::Right, Keypress::Down];
+    let player: Replay = replay(&[&shared[..], &[Keypress::Left, Keypress::Up]].concat());
+    let ai: Replay = replay(&[&shared[..], &[Keypress::Down, Keypress::Up, Keypress::Right]].concat());
+
+    assert_eq!(diff_replays(&player, &ai), [(5, Keypress::Left, Keypress::Down)]);
+    assert_eq!(trailing_moves(&player, &ai), [(7, Keypress::Right)]);
+    assert_eq!(trailing_moves(&ai, &player), [(7, Keypress::Right)]);
+}
+
+#[test]
+fn same_moves_no_differences() {
+    let moves: [Keypress; 3] = [Keypress::Left, Keypress::Undo, Keypress::Left];
+    assert!(diff_replays(&replay(&moves), &replay(&moves)).is_empty());
+    assert!(trailing_moves(&replay(&moves), &replay(&moves)).is_empty());
+}